@@ -4,7 +4,7 @@ import { monorepo } from "@condu-preset/monorepo/monorepo.js";
 export default configure(
   monorepo({
     gitignore: {
-      ignore: [".swc", "packages/swc-plugin-promock/target/"],
+      ignore: [".swc", "target/"],
     },
     moon: {
       toolchain: {
@@ -16,7 +16,7 @@ export default configure(
     vscode: {
       suggestedSettings: {
         "rust-analyzer.linkedProjects": [
-          "./packages/swc-plugin-promock/Cargo.toml",
+          "./Cargo.toml",
         ],
       },
     },
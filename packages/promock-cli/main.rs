@@ -0,0 +1,415 @@
+//! The `promock` CLI, running the transform of `promock_swc` on files and directories outside of an swc host,
+//! e.g. to pre-transform a package, or to see what the plugin emits for a module.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use promock_swc::{
+    promock_transform_with_source_map, relative_posix_path, Config, MODULE_EXTENSIONS,
+};
+use swc_core::{
+    common::{
+        chain,
+        comments::SingleThreadedComments,
+        errors::{ColorConfig, Handler, HANDLER},
+        source_map::SourceMapGenConfig,
+        sync::Lrc,
+        FileName, Globals, Mark, SourceMap, SourceMapper, GLOBALS,
+    },
+    ecma::{
+        ast::EsVersion,
+        codegen::{text_writer::JsWriter, Emitter},
+        parser::{parse_file_as_module, EsSyntax, Syntax, TsSyntax},
+        transforms::base::{fixer::fixer, hygiene::hygiene, resolver},
+        visit::FoldWith,
+    },
+};
+
+const USAGE: &str = "\
+Usage: promock [options] <file or directory>...
+
+Transforms the modules the same as the swc plugin, printing them to stdout,
+or writing them to the out directory along with the other files of the directories.
+
+Options:
+  -c, --config <file>   The config of the plugin, as JSON (the base path is relative to the working directory)
+  -d, --out-dir <dir>   The directory to write the output to, instead of stdout
+  -s, --source-maps     Write a source map next to each transformed module (requires --out-dir)
+  -e, --env <name>      The environment name matched against `envs` (defaults to SWC_ENV or NODE_ENV)
+  -x, --explain         Report what the transform did with each export to stderr (see `explain`)
+  -h, --help            Print this help";
+
+struct Options {
+    config: Config,
+    out_dir: Option<PathBuf>,
+    source_maps: bool,
+    env: Option<String>,
+    inputs: Vec<PathBuf>,
+}
+
+/// A file to write, at `output` relative to the out directory.
+struct InputFile {
+    path: PathBuf,
+    output: PathBuf,
+}
+
+struct Output {
+    code: String,
+    source_map: Option<Vec<u8>>,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&options) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the arguments, returning `None` when the help is requested.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut config_path = None;
+    let mut out_dir = None;
+    let mut source_maps = false;
+    let mut explain = false;
+    let mut env = env::var("SWC_ENV").or_else(|_| env::var("NODE_ENV")).ok();
+    let mut inputs = vec![];
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for {name}"))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-c" | "--config" => config_path = Some(value(&arg)?),
+            "-d" | "--out-dir" => out_dir = Some(PathBuf::from(value(&arg)?)),
+            "-s" | "--source-maps" => source_maps = true,
+            "-x" | "--explain" => explain = true,
+            "-e" | "--env" => env = Some(value(&arg)?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {arg}")),
+            _ => inputs.push(PathBuf::from(arg)),
+        }
+    }
+
+    if inputs.is_empty() {
+        return Err("no input files".into());
+    }
+    if source_maps && out_dir.is_none() {
+        return Err("--source-maps requires --out-dir".into());
+    }
+
+    let mut config = match config_path {
+        Some(config_path) => {
+            let json = fs::read_to_string(&config_path)
+                .map_err(|error| format!("failed to read {config_path}: {error}"))?;
+            serde_json::from_str::<Config>(&json)
+                .map_err(|error| format!("failed to parse {config_path}: {error}"))?
+        }
+        None => Config::default(),
+    };
+    config.explain |= explain;
+    // the paths are compared as absolute paths, so that inputs outside of the working directory work too
+    let cwd = env::current_dir().map_err(|error| error.to_string())?;
+    config.base_path = cwd.join(&config.base_path).to_string_lossy().into_owned();
+
+    Ok(Some(Options {
+        config,
+        out_dir,
+        source_maps,
+        env,
+        inputs,
+    }))
+}
+
+/// Transforms the inputs, returning whether all the modules were transformed without errors.
+fn run(options: &Options) -> io::Result<bool> {
+    let cwd = env::current_dir()?;
+    let enabled = options.config.is_enabled_in_env(options.env.as_deref());
+
+    let mut files = vec![];
+    for input in &options.inputs {
+        if input.is_dir() {
+            collect_files(input, Path::new(""), &mut files)?;
+        } else {
+            let file_name = input.file_name().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} is not a file", input.display()),
+                )
+            })?;
+            files.push(InputFile {
+                path: input.clone(),
+                output: PathBuf::from(file_name),
+            });
+        }
+    }
+
+    // e.g. `src/index.ts` and `lib/index.ts` would both be written to `<out dir>/index.ts`
+    if options.out_dir.is_some() {
+        let mut outputs = HashMap::new();
+        for file in &files {
+            if let Some(other) = outputs.insert(&file.output, &file.path) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "{} and {} would both be written to {} in the out directory",
+                        other.display(),
+                        file.path.display(),
+                        file.output.display()
+                    ),
+                ));
+            }
+        }
+    }
+
+    let mut success = true;
+    let mut stdout = io::stdout().lock();
+    for file in &files {
+        let path = cwd.join(&file.path);
+        let is_module = is_module(&path);
+        let relative_path = relative_posix_path(&options.config.base_path, &path.to_string_lossy());
+        // the overrides are applied by the transform itself, they're only needed here for the exclusions
+        let config = options.config.for_path(&relative_path);
+        let exclusion = config.path_exclusion(&relative_path);
+        let included = enabled && is_module && exclusion.is_none();
+        if let (true, true, Some(reason)) = (config.explain, is_module, exclusion) {
+            eprintln!(
+                "promock: {} is not transformed, as {reason}",
+                file.path.display()
+            );
+        }
+
+        match &options.out_dir {
+            Some(out_dir) => {
+                let output_path = out_dir.join(&file.output);
+                if let Some(parent) = output_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                if !included {
+                    fs::copy(&path, &output_path)?;
+                    continue;
+                }
+
+                let map_path = options.source_maps.then(|| {
+                    let mut map_path = output_path.clone().into_os_string();
+                    map_path.push(".map");
+                    PathBuf::from(map_path)
+                });
+                let Some(output) = transform_file(&options.config, &path, map_path.as_deref())?
+                else {
+                    success = false;
+                    continue;
+                };
+
+                let mut code = output.code;
+                if let (Some(map_path), Some(source_map)) = (&map_path, &output.source_map) {
+                    fs::write(map_path, source_map)?;
+                    let map_name = map_path.file_name().unwrap_or_default().to_string_lossy();
+                    code.push_str(&format!("//# sourceMappingURL={map_name}\n"));
+                }
+                fs::write(&output_path, code)?;
+            }
+            None => {
+                // only the modules are printed, as the other files of the directories are only copied along
+                if !is_module {
+                    continue;
+                }
+                if files.len() > 1 {
+                    writeln!(stdout, "// {}", file.path.display())?;
+                }
+                if !included {
+                    stdout.write_all(&fs::read(&path)?)?;
+                    continue;
+                }
+                match transform_file(&options.config, &path, None)? {
+                    Some(output) => stdout.write_all(output.code.as_bytes())?,
+                    None => success = false,
+                }
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Collects the files of the directory recursively, in a stable order, with their outputs relative to `output`.
+fn collect_files(dir: &Path, output: &Path, files: &mut Vec<InputFile>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let output = output.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_files(&path, &output, files)?;
+        } else {
+            files.push(InputFile { path, output });
+        }
+    }
+
+    Ok(())
+}
+
+fn is_module(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| MODULE_EXTENSIONS.contains(&extension))
+        && !path.to_string_lossy().ends_with(".d.ts")
+}
+
+/// Returns the syntax of the module, from its extension.
+fn syntax(path: &Path) -> Syntax {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("ts" | "mts" | "cts") => Syntax::Typescript(TsSyntax::default()),
+        Some("tsx") => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsSyntax {
+            jsx: true,
+            import_attributes: true,
+            ..Default::default()
+        }),
+    }
+}
+
+/// Parses and transforms the module at `path`, the same as swc would with the plugin,
+/// along with its source map when `map_path` is given.
+///
+/// The diagnostics are reported to stderr, returning `None` when there are errors.
+fn transform_file(
+    config: &Config,
+    path: &Path,
+    map_path: Option<&Path>,
+) -> io::Result<Option<Output>> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.load_file(path)?;
+    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+    let comments = SingleThreadedComments::default();
+    let syntax = syntax(path);
+
+    let output = GLOBALS.set(&Globals::new(), || {
+        HANDLER.set(&handler, || {
+            let mut recovered_errors = vec![];
+            let module = parse_file_as_module(
+                &fm,
+                syntax,
+                EsVersion::latest(),
+                Some(&comments),
+                &mut recovered_errors,
+            );
+            for error in recovered_errors {
+                error.into_diagnostic(&handler).emit();
+            }
+            let module = match module {
+                Ok(module) => module,
+                Err(error) => {
+                    error.into_diagnostic(&handler).emit();
+                    return None;
+                }
+            };
+
+            let source_map: Lrc<dyn SourceMapper> = cm.clone();
+            let module = module.fold_with(&mut chain!(
+                resolver(Mark::new(), Mark::new(), syntax.typescript()),
+                promock_transform_with_source_map(
+                    config.clone(),
+                    &path.to_string_lossy(),
+                    source_map
+                ),
+                hygiene(),
+                fixer(Some(&comments))
+            ));
+            if handler.has_errors() {
+                return None;
+            }
+
+            let mut code = vec![];
+            let mut mappings = vec![];
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                comments: Some(&comments),
+                wr: JsWriter::new(
+                    cm.clone(),
+                    "\n",
+                    &mut code,
+                    map_path.is_some().then_some(&mut mappings),
+                ),
+            };
+            emitter
+                .emit_module(&module)
+                .expect("failed to write to a buffer");
+
+            Some((code, mappings))
+        })
+    });
+
+    let Some((code, mappings)) = output else {
+        return Ok(None);
+    };
+
+    let source_map = match map_path {
+        Some(map_path) => {
+            let map_dir = map_path.parent().unwrap_or(Path::new(""));
+            let map_dir = env::current_dir()?.join(map_dir);
+            let mut source_map = vec![];
+            cm.build_source_map_with_config(
+                &mappings,
+                None,
+                RelativeSourceMapConfig {
+                    map_dir: map_dir.to_string_lossy().into_owned(),
+                },
+            )
+            .to_writer(&mut source_map)
+            .map_err(io::Error::other)?;
+            Some(source_map)
+        }
+        None => None,
+    };
+
+    Ok(Some(Output {
+        code: String::from_utf8(code).map_err(io::Error::other)?,
+        source_map,
+    }))
+}
+
+/// Refers to the sources relatively to the source map, and embeds their contents,
+/// so that the output can be moved around (e.g. vendored) along with its maps.
+struct RelativeSourceMapConfig {
+    map_dir: String,
+}
+
+impl SourceMapGenConfig for RelativeSourceMapConfig {
+    fn file_name_to_source(&self, file_name: &FileName) -> String {
+        match file_name {
+            FileName::Real(path) => relative_posix_path(&self.map_dir, &path.to_string_lossy()),
+            _ => file_name.to_string(),
+        }
+    }
+
+    fn inline_sources_content(&self, _file_name: &FileName) -> bool {
+        true
+    }
+}
@@ -0,0 +1,247 @@
+//! Runs the `promock` binary on projects written to a temporary directory, as it would be run from their root.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+const MODULE: &str = "export const example = {};\n";
+
+/// Writes the files of the project to a fresh directory, returning its path.
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("cli")
+        .join(name);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(&dir).unwrap();
+    for (path, content) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+/// Runs `promock` in the directory, regardless of the environment of the tests.
+fn promock(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_promock"))
+        .current_dir(dir)
+        .args(args)
+        .env_remove("SWC_ENV")
+        .env_remove("NODE_ENV")
+        .output()
+        .expect("failed to run promock")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn read(path: PathBuf) -> String {
+    fs::read_to_string(&path).unwrap_or_else(|error| panic!("{}: {error}", path.display()))
+}
+
+#[test]
+fn prints_help() {
+    let dir = project("prints_help", &[]);
+
+    for flag in ["-h", "--help"] {
+        let output = promock(&dir, &[flag]);
+        assert!(output.status.success());
+        assert!(stdout(&output).starts_with("Usage: promock"), "{output:?}");
+    }
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    let dir = project("rejects_invalid_arguments", &[("example.js", MODULE)]);
+
+    for (args, error) in [
+        (&[][..], "error: no input files"),
+        (
+            &["--unknown", "example.js"][..],
+            "error: unknown option --unknown",
+        ),
+        (
+            &["example.js", "--config"][..],
+            "error: missing value for --config",
+        ),
+        (
+            &["--source-maps", "example.js"][..],
+            "error: --source-maps requires --out-dir",
+        ),
+        (
+            &["--config", "missing.json", "example.js"][..],
+            "error: failed to read missing.json",
+        ),
+    ] {
+        let output = promock(&dir, args);
+        assert!(!output.status.success(), "{args:?}");
+        assert!(stderr(&output).starts_with(error), "{args:?}: {output:?}");
+    }
+}
+
+#[test]
+fn prints_modules_to_stdout() {
+    let dir = project(
+        "prints_modules_to_stdout",
+        &[("src/example.js", MODULE), ("src/README.md", "# example\n")],
+    );
+
+    let output = promock(&dir, &["src/example.js"]);
+    assert!(output.status.success(), "{output:?}");
+    let code = stdout(&output);
+    assert!(code.starts_with("\"use __mockified__\";\n"), "{code}");
+    assert!(code.contains("path: \"src/example.js\""), "{code}");
+
+    // with several modules, each is preceded by its path, and the other files are left out
+    let output = promock(&dir, &["src"]);
+    assert!(output.status.success(), "{output:?}");
+    let code = stdout(&output);
+    assert!(code.starts_with("// src/example.js\n"), "{code}");
+    assert!(!code.contains("README"), "{code}");
+}
+
+#[test]
+fn writes_directories_to_out_dir() {
+    let dir = project(
+        "writes_directories_to_out_dir",
+        &[
+            ("src/index.ts", MODULE),
+            ("src/lib/util.ts", MODULE),
+            ("src/lib/types.d.ts", "export type Example = {};\n"),
+            ("src/data.json", "{}\n"),
+        ],
+    );
+
+    let output = promock(&dir, &["src", "--out-dir", "out", "--source-maps"]);
+    assert!(output.status.success(), "{output:?}");
+
+    // the paths are relative to the directory, the same as its modules
+    let code = read(dir.join("out/lib/util.ts"));
+    assert!(code.contains("path: \"src/lib/util.ts\""), "{code}");
+    assert!(
+        code.ends_with("//# sourceMappingURL=util.ts.map\n"),
+        "{code}"
+    );
+    let source_map = read(dir.join("out/lib/util.ts.map"));
+    assert!(
+        source_map.contains("\"../../src/lib/util.ts\""),
+        "{source_map}"
+    );
+    assert!(read(dir.join("out/index.ts")).contains("mockify"));
+
+    // the other files are copied as is
+    assert_eq!(
+        read(dir.join("out/lib/types.d.ts")),
+        "export type Example = {};\n"
+    );
+    assert_eq!(read(dir.join("out/data.json")), "{}\n");
+}
+
+#[test]
+fn writes_files_to_out_dir_by_name() {
+    let dir = project(
+        "writes_files_to_out_dir_by_name",
+        &[("src/a/example.js", MODULE), ("src/b/other.js", MODULE)],
+    );
+
+    let output = promock(&dir, &["src/a/example.js", "src/b/other.js", "-d", "out"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(read(dir.join("out/example.js")).contains("path: \"src/a/example.js\""));
+    assert!(read(dir.join("out/other.js")).contains("path: \"src/b/other.js\""));
+}
+
+#[test]
+fn rejects_colliding_outputs() {
+    let dir = project(
+        "rejects_colliding_outputs",
+        &[("src/index.js", MODULE), ("lib/index.js", MODULE)],
+    );
+
+    let output = promock(&dir, &["src/index.js", "lib/index.js", "-d", "out"]);
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains(
+            "src/index.js and lib/index.js would both be written to index.js in the out directory"
+        ),
+        "{output:?}"
+    );
+    assert!(!dir.join("out").exists());
+
+    // the same goes for the files of directories
+    let output = promock(&dir, &["src", "lib", "-d", "out"]);
+    assert!(!output.status.success());
+    assert!(!dir.join("out").exists());
+}
+
+#[test]
+fn leaves_excluded_modules() {
+    let dir = project(
+        "leaves_excluded_modules",
+        &[
+            (
+                "promock.json",
+                r#"{ "basePath": ".", "includePaths": ["^src/"], "excludePaths": ["^src/vendor/"] }"#,
+            ),
+            ("src/example.js", MODULE),
+            ("src/vendor/library.js", MODULE),
+            ("scripts/build.js", MODULE),
+        ],
+    );
+
+    let output = promock(
+        &dir,
+        &["-c", "promock.json", "src", "scripts", "-d", "out", "-x"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(read(dir.join("out/example.js")).contains("mockify"));
+    assert_eq!(read(dir.join("out/vendor/library.js")), MODULE);
+    assert_eq!(read(dir.join("out/build.js")), MODULE);
+
+    let stderr = stderr(&output);
+    assert!(
+        stderr.contains(
+            "src/vendor/library.js is not transformed, as its path matches `excludePaths`"
+        ),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains(
+            "scripts/build.js is not transformed, as its path doesn't match `includePaths`"
+        ),
+        "{stderr}"
+    );
+
+    // excluded modules are printed as is
+    let output = promock(&dir, &["-c", "promock.json", "src/vendor/library.js"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(stdout(&output), MODULE);
+}
+
+#[test]
+fn only_transforms_in_envs() {
+    let dir = project(
+        "only_transforms_in_envs",
+        &[
+            ("promock.json", r#"{ "basePath": ".", "envs": ["test"] }"#),
+            ("example.js", MODULE),
+        ],
+    );
+
+    let output = promock(
+        &dir,
+        &["-c", "promock.json", "-e", "production", "example.js"],
+    );
+    assert_eq!(stdout(&output), MODULE);
+
+    let output = promock(&dir, &["-c", "promock.json", "--env", "test", "example.js"]);
+    assert!(stdout(&output).contains("mockify"), "{output:?}");
+}
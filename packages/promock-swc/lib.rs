@@ -0,0 +1,3308 @@
+use serde::Deserialize;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt,
+};
+
+use regex::Regex;
+use swc_core::{
+    common::{
+        errors::{SourceMapper, HANDLER},
+        pass::Optional,
+        sync::Lrc,
+        util::take::Take,
+        Mark, Span, SyntaxContext, DUMMY_SP,
+    },
+    ecma::{
+        ast::{
+            ArrayLit, BindingIdent, BlockStmt, Bool, CallExpr, Callee, ClassDecl, ClassExpr,
+            CondExpr, Decl, DefaultDecl, ExportDecl, ExportDefaultDecl, ExportDefaultExpr,
+            ExportNamedSpecifier, ExportSpecifier, Expr, ExprOrSpread, ExprStmt, FnDecl, Function,
+            GetterProp, Id, Ident, IdentName, ImportDecl, ImportNamedSpecifier, ImportPhase,
+            ImportSpecifier, JSXElementName, JSXObject, KeyValueProp, Lit, MemberExpr, MemberProp,
+            Module, ModuleDecl, ModuleExportName, ModuleItem, NamedExport, Number, ObjectLit,
+            Param, Pat, Prop, PropName, PropOrSpread, RestPat, ReturnStmt, SimpleAssignTarget,
+            Stmt, Str, ThisExpr, TsExportAssignment, TsModuleBlock, TsModuleDecl, TsModuleName,
+            TsNamespaceBody, TsNamespaceDecl, UnaryExpr, UnaryOp, VarDecl, VarDeclKind,
+            VarDeclarator,
+        },
+        atoms::JsWord,
+        visit::{as_folder, Fold, Folder, Visit, VisitMut, VisitMutWith, VisitWith},
+    },
+};
+use swc_ecma_utils::find_pat_ids;
+
+/// Opts a module out of the transform.
+const DO_NOT_MOCKIFY_DIRECTIVE: &str = "use __do_not_mockify__";
+/// Added to the top of transformed modules, so that they're never transformed twice.
+const MOCKIFIED_DIRECTIVE: &str = "use __mockified__";
+
+/// Static plugin configuration.
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The name of the module to import mockify from.
+    #[serde(default = "default_import_from")]
+    pub import_from: String,
+
+    #[serde(default = "default_import_as")]
+    pub import_as: String,
+
+    #[serde(default = "default_export_name")]
+    pub export_name: String,
+
+    /// The base directory to use for relative paths.
+    #[serde()]
+    pub base_path: String,
+
+    /// Patterns of the paths of the modules to transform, relative to `base_path` as POSIX paths,
+    /// the modules outside of it starting with `../` (or being absolute on another drive), see `relative_posix_path`.
+    #[serde(default = "default_include_paths", with = "serde_regex")]
+    pub include_paths: Option<Vec<Regex>>,
+
+    /// Patterns of the paths of the modules to leave as is, matched the same as `include_paths`.
+    #[serde(default = "default_exclude_paths", with = "serde_regex")]
+    pub exclude_paths: Option<Vec<Regex>>,
+
+    /// How the transformed modules get a hold of mockify.
+    #[serde(default)]
+    pub runtime: Runtime,
+
+    /// Whether to export a registry of the mockified exports from each module, as `__promock_module__`,
+    /// for runtime helpers working on whole modules (e.g. `restoreModule`).
+    /// Off by default, as it adds an export to every transformed module.
+    #[serde(default)]
+    pub module_registry: bool,
+
+    /// Whether to also mockify the module-level functions, classes and consts that are not exported,
+    /// so that tests can override the internals of a module, exposed by name as `__promock_rewire__`.
+    #[serde(default)]
+    pub rewire: bool,
+
+    /// Import specifiers (e.g. `^lodash$`) of modules that are not transformed themselves (e.g. dependencies),
+    /// whose named and default imports are mockified by the importing modules instead.
+    /// The proxies are shared by all the importing modules, keyed by the module and the name of the import.
+    /// Note that the imports stop being live bindings.
+    #[serde(default = "default_mock_imports", with = "serde_regex")]
+    pub mock_imports: Option<Vec<Regex>>,
+
+    /// Whether to mockify the default imports of JSON modules, and of the other modules imported with
+    /// attributes (`import data from "./data.json" with { type: "json" }`), the same as `mock_imports`,
+    /// so that their data can be overridden.
+    #[serde(default)]
+    pub mock_attribute_imports: bool,
+
+    /// The environments to transform modules in, matched against the environment name of swc
+    /// (`envName`, which defaults to `SWC_ENV` or `NODE_ENV`), e.g. `["test"]`,
+    /// so that a configuration shared with production builds leaves their modules untouched.
+    /// When not defined, modules are transformed in every environment.
+    #[serde(default)]
+    pub envs: Option<Vec<String>>,
+
+    /// Whether to report what the transform did with each export as warnings,
+    /// i.e. whether it was mockified, or why it was left as is (and why a whole module was left as is),
+    /// to find out why an export isn't mocked.
+    #[serde(default)]
+    pub explain: bool,
+
+    /// Whether to report an error for every export that cannot be mockified
+    /// (e.g. `export let`, `export declare`, re-exports), so that tests don't silently run against
+    /// the real implementation. Exports that only exist at the type level are not reported.
+    #[serde(default)]
+    pub strict: bool,
+
+    /// Exports that are intentionally left as is in `strict` mode,
+    /// matched against `<path>#<export name>`, e.g. `^src/legacy/` or `^src/config\.ts#debug$`.
+    #[serde(default = "default_strict_allowlist", with = "serde_regex")]
+    pub strict_allowlist: Option<Vec<Regex>>,
+
+    /// Whether to check that the transform leaves the exports of each module as they were
+    /// (their names, whether they are types, default exports, etc.), reporting an error otherwise.
+    /// A debugging aid for the transform itself, always on in its tests.
+    #[serde(default)]
+    pub verify_exports: bool,
+
+    /// Settings of the modules whose paths match, applied in order onto the rest of the config,
+    /// e.g. `{ "paths": ["^packages/app/"], "importFrom": "@app/promock" }` (see `ConfigOverride`).
+    #[serde(default)]
+    pub overrides: Vec<ConfigOverride>,
+}
+
+/// Settings overriding the config for the modules whose paths match `paths`,
+/// relative to `base_path` (the same as `include_paths`); the settings that are not given are left as they are.
+///
+/// The settings that decide whether modules are transformed at all (`include_paths`, `exclude_paths`, `envs`)
+/// cannot be overridden, only by the paths themselves.
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct ConfigOverride {
+    #[serde(with = "serde_regex")]
+    pub paths: Vec<Regex>,
+
+    pub import_from: Option<String>,
+    pub import_as: Option<String>,
+    pub export_name: Option<String>,
+    pub runtime: Option<Runtime>,
+    pub module_registry: Option<bool>,
+    pub rewire: Option<bool>,
+    #[serde(default, with = "serde_regex")]
+    pub mock_imports: Option<Vec<Regex>>,
+    pub mock_attribute_imports: Option<bool>,
+    pub explain: Option<bool>,
+    pub strict: Option<bool>,
+    #[serde(default, with = "serde_regex")]
+    pub strict_allowlist: Option<Vec<Regex>>,
+    pub verify_exports: Option<bool>,
+}
+
+impl ConfigOverride {
+    fn apply(&self, config: &mut Config) {
+        macro_rules! override_settings {
+            ($($setting:ident),*) => {
+                $(if let Some(value) = &self.$setting {
+                    config.$setting = value.clone();
+                })*
+            };
+        }
+        override_settings!(
+            import_from,
+            import_as,
+            export_name,
+            runtime,
+            module_registry,
+            rewire,
+            mock_attribute_imports,
+            explain,
+            strict,
+            verify_exports
+        );
+        if let Some(mock_imports) = &self.mock_imports {
+            config.mock_imports = Some(mock_imports.clone());
+        }
+        if let Some(strict_allowlist) = &self.strict_allowlist {
+            config.strict_allowlist = Some(strict_allowlist.clone());
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            import_from: default_import_from(),
+            import_as: default_import_as(),
+            export_name: default_export_name(),
+            base_path: ".".into(),
+            include_paths: default_include_paths(),
+            exclude_paths: default_exclude_paths(),
+            runtime: Runtime::default(),
+            module_registry: false,
+            rewire: false,
+            mock_imports: default_mock_imports(),
+            mock_attribute_imports: false,
+            envs: None,
+            explain: false,
+            strict: false,
+            strict_allowlist: default_strict_allowlist(),
+            verify_exports: false,
+            overrides: vec![],
+        }
+    }
+}
+
+/// The ways of delivering mockify to the transformed modules.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Runtime {
+    /// `import { mockify } from "promock";`
+    #[default]
+    Import,
+    /// `const mockify = require("promock").mockify;`
+    Require,
+    /// `const mockify = globalThis.__promock__.mockify;`,
+    /// for environments where `import_from` cannot be resolved (browsers, workers, sandboxes).
+    /// The runtime must be installed beforehand (see `installGlobalRuntime` of promock).
+    Global,
+    /// A helper function inlined into each module, which mockifies using the global runtime when installed,
+    /// or returns the value as is otherwise.
+    Inline,
+}
+
+/// The name of the global object holding the runtime, used by `Runtime::Global` and `Runtime::Inline`.
+const RUNTIME_GLOBAL: &str = "__promock__";
+
+/// The name of the export holding the module registry (see `Config::module_registry`).
+/// Exports of ES modules are always enumerable, so it's kept out of the way by its name instead.
+const MODULE_REGISTRY_EXPORT: &str = "__promock_module__";
+
+/// The name of the export holding the accessor of the rewired bindings (see `Config::rewire`).
+const REWIRE_EXPORT: &str = "__promock_rewire__";
+
+// when not defined, include all paths by default
+fn default_include_paths() -> Option<Vec<Regex>> {
+    None
+}
+fn default_exclude_paths() -> Option<Vec<Regex>> {
+    None
+}
+fn default_mock_imports() -> Option<Vec<Regex>> {
+    None
+}
+fn default_strict_allowlist() -> Option<Vec<Regex>> {
+    None
+}
+fn default_import_as() -> String {
+    "mockify".into()
+}
+fn default_import_from() -> String {
+    "promock".into()
+}
+fn default_export_name() -> String {
+    "mockify".into()
+}
+
+/// The kind of an export, as reported to mockify in the metadata of the export.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ExportKind {
+    Const,
+    Function,
+    Class,
+    Enum,
+    Namespace,
+    /// `export { a }` or `export default a`, where the declaration of `a` is not looked into
+    Binding,
+    /// `export default <expression>`
+    Expression,
+    /// `export = <expression>`
+    ExportAssignment,
+    /// A binding imported by the module (see `Config::mock_imports`)
+    Import,
+}
+
+impl ExportKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExportKind::Const => "const",
+            ExportKind::Function => "function",
+            ExportKind::Class => "class",
+            ExportKind::Enum => "enum",
+            ExportKind::Namespace => "namespace",
+            ExportKind::Binding => "binding",
+            ExportKind::Expression => "expression",
+            ExportKind::ExportAssignment => "exportAssignment",
+            ExportKind::Import => "import",
+        }
+    }
+}
+
+/// Where a mockified value comes from, passed to mockify as its third argument:
+/// `mockify(value, wrapper, { path, exportName, kind, line, column })`
+struct ExportMetadata {
+    export_name: String,
+    kind: ExportKind,
+    /// Span of the export in the source, for the line and column
+    span: Span,
+    origin: BindingOrigin,
+}
+
+/// Whether a mockified value is exported by the module, or comes from one of its options
+#[derive(Clone, Debug, PartialEq)]
+enum BindingOrigin {
+    Export,
+    /// A binding that is not exported, mockified by `Config::rewire`
+    Rewired,
+    /// A binding imported from the given module (see `Config::mock_imports` and `Config::mock_attribute_imports`),
+    /// whose proxy is shared by all the modules importing it
+    Import(String),
+}
+
+impl ExportMetadata {
+    fn new(export_name: impl Into<String>, kind: ExportKind, span: Span) -> Self {
+        Self {
+            export_name: export_name.into(),
+            kind,
+            span,
+            origin: BindingOrigin::Export,
+        }
+    }
+
+    fn rewired(name: impl Into<String>, kind: ExportKind, span: Span) -> Self {
+        Self {
+            origin: BindingOrigin::Rewired,
+            ..Self::new(name, kind, span)
+        }
+    }
+
+    fn imported(import_name: impl Into<String>, imported_from: String, span: Span) -> Self {
+        Self {
+            origin: BindingOrigin::Import(imported_from),
+            ..Self::new(import_name, ExportKind::Import, span)
+        }
+    }
+}
+
+/// What the transform did with an export, reported with `Config::explain`
+struct ExportReport {
+    export_name: String,
+    span: Span,
+    decision: ExportDecision,
+}
+
+enum ExportDecision {
+    Mockified(ExportKind, BindingOrigin),
+    Skipped(SkipReason),
+}
+
+/// Why an export is left as is
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SkipReason {
+    /// `export let` and `export var`, which can be reassigned by the module
+    Mutable,
+    /// `export declare`, which has no runtime value
+    Ambient,
+    /// `export const enum`, which is inlined by TypeScript
+    ConstEnum,
+    /// Interfaces, type aliases, `export type`, etc.
+    TypeOnly,
+    /// `export { a } from "./a"` and `export * from "./a"`
+    ReExport,
+    /// `export class A {}`
+    ClassDeclaration,
+    /// `export const { a } = b` and `export const [a] = b`
+    Destructured,
+}
+
+impl SkipReason {
+    /// Whether the export has a runtime value that is left as is (see `Config::strict`)
+    fn is_unmockable(&self) -> bool {
+        *self != SkipReason::TypeOnly
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::Mutable => "`let` and `var` exports can be reassigned, only `const` exports are",
+            SkipReason::Ambient => "ambient declarations have no runtime value",
+            SkipReason::ConstEnum => "const enums are inlined by TypeScript",
+            SkipReason::TypeOnly => "it only exists at the type level",
+            SkipReason::ReExport => {
+                "re-exports are left to the module they come from, which is mockified if it's included"
+            }
+            SkipReason::ClassDeclaration => {
+                "class declarations are not mockified where they're exported, \
+                 export the class with `export { A }` or `export default class` instead"
+            }
+            SkipReason::Destructured => {
+                "destructured bindings are not mockified, \
+                 declare them with `const` and export them with `export { a }` instead"
+            }
+        }
+    }
+}
+
+impl ExportReport {
+    fn message(&self) -> String {
+        let name = format!("`{}`", self.export_name);
+        match &self.decision {
+            ExportDecision::Mockified(kind, BindingOrigin::Export) => {
+                format!("promock: {name} is mockified ({})", kind.as_str())
+            }
+            ExportDecision::Mockified(kind, BindingOrigin::Rewired) => {
+                format!("promock: {name} is rewired ({})", kind.as_str())
+            }
+            ExportDecision::Mockified(_, BindingOrigin::Import(imported_from)) => {
+                format!("promock: the import {name} from \"{imported_from}\" is mockified")
+            }
+            ExportDecision::Skipped(reason) => {
+                format!("promock: {name} is not mockified, as {}", reason.as_str())
+            }
+        }
+    }
+}
+
+/// Returns the name a specifier is exported as, and its span
+fn specifier_export_name(specifier: &ExportSpecifier) -> (String, Span) {
+    match specifier {
+        ExportSpecifier::Named(named_specifier) => {
+            let exported = match named_specifier
+                .exported
+                .as_ref()
+                .unwrap_or(&named_specifier.orig)
+            {
+                ModuleExportName::Ident(ident) => ident.sym.to_string(),
+                ModuleExportName::Str(str) => str.value.to_string(),
+            };
+            (exported, named_specifier.span)
+        }
+        ExportSpecifier::Namespace(namespace_specifier) => match &namespace_specifier.name {
+            ModuleExportName::Ident(ident) => (ident.sym.to_string(), namespace_specifier.span),
+            ModuleExportName::Str(str) => (str.value.to_string(), namespace_specifier.span),
+        },
+        ExportSpecifier::Default(default_specifier) => (
+            default_specifier.exported.sym.to_string(),
+            default_specifier.exported.span,
+        ),
+    }
+}
+
+/// Returns the names and spans of the exported declarations that are left as is, and why.
+/// The other declarations are mockified, or reported when their members are (e.g. namespaces).
+fn skipped_declarations(decl: &Decl) -> Vec<(String, Span, SkipReason)> {
+    let (ident, reason) = match decl {
+        Decl::Var(var_decl) => {
+            let reason = if var_decl.declare {
+                SkipReason::Ambient
+            } else if var_decl.kind != VarDeclKind::Const {
+                SkipReason::Mutable
+            } else {
+                SkipReason::Destructured
+            };
+            return var_decl
+                .decls
+                .iter()
+                .filter(|decl| {
+                    reason != SkipReason::Destructured || !matches!(decl.name, Pat::Ident(..))
+                })
+                .flat_map(|decl| find_pat_ids::<_, Ident>(&decl.name))
+                .map(|ident| (ident.sym.to_string(), ident.span, reason))
+                .collect();
+        }
+        Decl::Fn(fn_decl) if fn_decl.declare => (&fn_decl.ident, SkipReason::Ambient),
+        Decl::Class(class_decl) if class_decl.declare => (&class_decl.ident, SkipReason::Ambient),
+        Decl::Class(class_decl) => (&class_decl.ident, SkipReason::ClassDeclaration),
+        Decl::TsEnum(enum_decl) if enum_decl.declare => (&enum_decl.id, SkipReason::Ambient),
+        Decl::TsEnum(enum_decl) if enum_decl.is_const => (&enum_decl.id, SkipReason::ConstEnum),
+        Decl::TsModule(module_decl) => {
+            let TsModuleName::Ident(ident) = &module_decl.id else {
+                return vec![];
+            };
+            if module_decl.declare {
+                (ident, SkipReason::Ambient)
+            } else if !is_namespace_instantiated(module_decl.body.as_ref()) {
+                (ident, SkipReason::TypeOnly)
+            } else {
+                return vec![];
+            }
+        }
+        Decl::TsInterface(interface_decl) => (&interface_decl.id, SkipReason::TypeOnly),
+        Decl::TsTypeAlias(type_alias_decl) => (&type_alias_decl.id, SkipReason::TypeOnly),
+        _ => return vec![],
+    };
+    vec![(ident.sym.to_string(), ident.span, reason)]
+}
+
+/// Wraps the exports of a module with `mockify`.
+///
+/// The module is expected to be resolved (see `swc_core::ecma::transforms::base::resolver`),
+/// as is the case for programs passed to plugins, so that bindings are compared by their syntax context,
+/// and not only by their name.
+pub struct TransformVisitor {
+    config: Config,
+    mockify_used: bool, // Add a flag to know if mockify was used
+    do_not_mockify: bool,
+    added_to_top_of_file: Vec<ModuleItem>,
+    added_to_bottom_of_file: Vec<ModuleItem>,
+    /// Proxies of the imports (see `Config::mock_imports`) and of the functions,
+    /// which the module may call while it's evaluated, as function declarations are hoisted
+    added_after_imports: Vec<ModuleItem>,
+    /// Syntax context of the bindings generated by the transform (see `private_ident`)
+    private_ctxt: SyntaxContext,
+    /// Local binding of the imported `mockify` function
+    mockify_ident: Ident,
+    /// Every binding that was mockified, mapped to the identifier holding its proxy,
+    /// so that all exports of the same binding share a single proxy
+    mockified_identifiers: HashMap<Id, Ident>,
+    /// Exported enums and namespaces, which may be declared more than once (declaration merging)
+    exported_declarations: HashSet<Id>,
+    /// TypeScript overload signatures seen since the last function implementation
+    overload_signatures: Vec<FnDecl>,
+    /// Module-level bindings that have no runtime value (interfaces, type aliases, ambient declarations, etc.)
+    type_only_identifiers: HashSet<Id>,
+    /// Module-level function declarations, whose proxies go right after the imports (see `added_after_imports`)
+    function_declarations: HashSet<Id>,
+    /// Path of the module, relative to `base_path`, reported in the metadata of the exports
+    file_path: Option<String>,
+    /// Used to report the line and column of the exports in their metadata
+    source_map: Option<Lrc<dyn SourceMapper>>,
+    /// Names of the namespaces being visited, prefixed to the export names of their members
+    namespace_path: Vec<JsWord>,
+    /// Names and kinds of the mockified exports, listed in the module registry
+    registered_exports: Vec<(String, ExportKind)>,
+    /// `export =` cannot be used along with other exports, so the module registry is left out
+    has_export_assignment: bool,
+    /// Module-level bindings that are not rewired (see `collect_rewire_exclusions`)
+    rewire_exclusions: HashSet<Id>,
+    /// Names of the rewired bindings, mapped to the identifiers holding their proxies
+    rewired_bindings: Vec<(JsWord, Ident)>,
+    /// Module-level bindings referenced as values, as opposed to types (see `Config::mock_imports`)
+    value_references: HashSet<Id>,
+    /// What the transform did with each export (see `Config::explain`)
+    reports: Vec<ExportReport>,
+    /// Why the whole module is left as is, e.g. it's excluded by its path, or it has an opt-out directive
+    module_exclusion: Option<&'static str>,
+}
+
+impl TransformVisitor {
+    pub fn new(config: Option<Config>) -> Self {
+        Self {
+            config: config.unwrap_or_default(),
+            added_to_bottom_of_file: vec![],
+            added_to_top_of_file: vec![],
+            added_after_imports: vec![],
+            private_ctxt: SyntaxContext::empty(),
+            mockify_ident: Ident::new(default_import_as().into(), DUMMY_SP, Default::default()),
+            mockify_used: false,
+            do_not_mockify: false,
+            mockified_identifiers: HashMap::new(),
+            exported_declarations: HashSet::new(),
+            overload_signatures: vec![],
+            type_only_identifiers: HashSet::new(),
+            function_declarations: HashSet::new(),
+            file_path: None,
+            source_map: None,
+            namespace_path: vec![],
+            registered_exports: vec![],
+            has_export_assignment: false,
+            rewire_exclusions: HashSet::new(),
+            rewired_bindings: vec![],
+            value_references: HashSet::new(),
+            reports: vec![],
+            module_exclusion: None,
+        }
+    }
+
+    /// Sets the module being transformed, so that the metadata passed to mockify
+    /// includes its path (relative to `base_path`) and the location of the exports in it.
+    pub fn with_source(mut self, file_path: String, source_map: Lrc<dyn SourceMapper>) -> Self {
+        self.file_path = Some(file_path);
+        self.source_map = Some(source_map);
+        self
+    }
+}
+
+impl TransformVisitor {
+    /// Adds `const _mockified_<name> = mockify(<name>);` to the bottom of the file,
+    /// or right after the imports for function declarations (see `added_after_imports`),
+    /// unless the binding was already mockified.
+    /// Returns the identifier holding the proxy of the binding, and whether it was newly created.
+    fn mockify_binding(&mut self, ident: &Ident, metadata: ExportMetadata) -> (Ident, bool) {
+        if let Some(mockified_ident) = self.mockified_identifiers.get(&ident.to_id()) {
+            return (mockified_ident.clone(), false);
+        }
+
+        let mockified_ident = self.private_ident(format!("_mockified_{}", ident.sym), DUMMY_SP);
+        self.mockify_used = true;
+        let mockify_stmt = Stmt::Decl(const_decl(
+            mockified_ident.clone(),
+            self.wrap_with_mockify(
+                DUMMY_SP,
+                Expr::Ident(Ident::new(ident.sym.clone(), DUMMY_SP, ident.ctxt)),
+                None,
+                metadata,
+            ),
+        ));
+        if self.function_declarations.contains(&ident.to_id()) {
+            self.added_after_imports
+                .push(ModuleItem::Stmt(mockify_stmt));
+        } else {
+            self.added_to_bottom_of_file
+                .push(ModuleItem::Stmt(mockify_stmt));
+        }
+
+        // Store this identifier as mockified
+        self.mockified_identifiers
+            .insert(ident.to_id(), mockified_ident.clone());
+
+        (mockified_ident, true)
+    }
+
+    /// `export default A` exports the value of `A` at that point, so its proxy is declared in place of it:
+    /// `const _mockified_A = mockify(A); export { _mockified_A as default };`,
+    /// unless `A` holds its proxy from its declaration on (`export const`, `export function`).
+    /// The proxy is shared with the other exports of `A`, moving it here when they come first.
+    fn mockify_default_export_of_binding(&mut self, ident: &Ident, span: Span) -> ModuleItem {
+        if let Some(mockified_ident) = self.mockified_identifiers.get(&ident.to_id()).cloned() {
+            self.register_export("default", ExportKind::Binding, span);
+            let export = ModuleItem::ModuleDecl(export_as(
+                mockified_ident.clone(),
+                default_module_export_name(),
+            ));
+            let proxy_index = self.added_to_bottom_of_file.iter().position(|item| {
+                matches!(item, ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl)))
+                    if matches!(&var_decl.decls[0].name, Pat::Ident(binding)
+                        if binding.id.to_id() == mockified_ident.to_id()))
+            });
+            return match proxy_index {
+                Some(index) => {
+                    self.added_to_bottom_of_file.push(export);
+                    self.added_to_bottom_of_file.remove(index)
+                }
+                None => export,
+            };
+        }
+
+        self.mockify_used = true;
+        let mockified_ident = self.private_ident(format!("_mockified_{}", ident.sym), DUMMY_SP);
+        self.mockified_identifiers
+            .insert(ident.to_id(), mockified_ident.clone());
+        let metadata = ExportMetadata::new("default", ExportKind::Binding, span);
+        let proxy = const_decl(
+            mockified_ident.clone(),
+            self.wrap_with_mockify(DUMMY_SP, Expr::Ident(ident.clone()), None, metadata),
+        );
+        self.added_to_bottom_of_file
+            .push(ModuleItem::ModuleDecl(export_as(
+                mockified_ident,
+                default_module_export_name(),
+            )));
+        ModuleItem::Stmt(Stmt::Decl(proxy))
+    }
+
+    /// Registers the bindings that are mockified at their declaration,
+    /// i.e. `export const` (which holds the proxy itself) and `export function` (`_mockified_<name>`),
+    /// so that other exports of these bindings reuse their proxy, regardless of the order of exports.
+    fn register_exported_declarations(&mut self, items: &[ModuleItem]) {
+        for item in items {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) = item else {
+                continue;
+            };
+            match &export.decl {
+                Decl::Var(var_decl) if var_decl.kind == VarDeclKind::Const && !var_decl.declare => {
+                    for decl in &var_decl.decls {
+                        if let (Pat::Ident(binding), Some(_)) = (&decl.name, &decl.init) {
+                            self.mockified_identifiers
+                                .insert(binding.id.to_id(), binding.id.clone());
+                        }
+                    }
+                }
+                Decl::Fn(fn_decl) if !fn_decl.declare && fn_decl.function.body.is_some() => {
+                    let mockified_ident = self.private_ident(
+                        format!("_mockified_{}", fn_decl.ident.sym),
+                        fn_decl.ident.span,
+                    );
+                    self.mockified_identifiers
+                        .insert(fn_decl.ident.to_id(), mockified_ident);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Replaces an exported enum or namespace with its non-exported declaration,
+    /// and exports its mockified version under the original name instead:
+    /// `const _mockified_<name> = mockify(<name>); export { _mockified_<name> as <name> };`
+    fn mockify_exported_declaration(
+        &mut self,
+        item: &mut ModuleItem,
+        ident: Ident,
+        decl: Decl,
+        kind: ExportKind,
+        span: Span,
+    ) {
+        *item = ModuleItem::Stmt(Stmt::Decl(decl));
+
+        // declaration merging means the same name may be exported more than once
+        if !self.exported_declarations.insert(ident.to_id()) {
+            return;
+        }
+        let metadata = ExportMetadata::new(ident.sym.to_string(), kind, span);
+        let (mockified_ident, created) = self.mockify_binding(&ident, metadata);
+        if !created {
+            self.register_export(&ident.sym, kind, span);
+        }
+        self.added_to_bottom_of_file
+            .push(ModuleItem::ModuleDecl(export_as(
+                mockified_ident,
+                ModuleExportName::Ident(ident),
+            )));
+    }
+}
+
+fn str_lit(value: &str) -> Str {
+    Str {
+        value: value.into(),
+        span: DUMMY_SP,
+        raw: None,
+    }
+}
+
+fn num_lit(value: f64) -> Number {
+    Number {
+        value,
+        span: DUMMY_SP,
+        raw: None,
+    }
+}
+
+/// Creates `<key>: <value>`
+fn key_value_prop(key: &str, value: Expr) -> PropOrSpread {
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(IdentName::new(key.into(), DUMMY_SP)),
+        value: Box::new(value),
+    })))
+}
+
+/// Creates `<obj>.<prop>`
+fn member_expr(obj: Expr, prop: &str) -> Expr {
+    Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(obj),
+        prop: MemberProp::Ident(IdentName::new(prop.into(), DUMMY_SP)),
+    })
+}
+
+/// Creates `globalThis.__promock__`
+fn global_runtime_expr() -> Expr {
+    member_expr(
+        Expr::Ident(Ident::new(
+            "globalThis".into(),
+            DUMMY_SP,
+            Default::default(),
+        )),
+        RUNTIME_GLOBAL,
+    )
+}
+
+/// Creates `const <name> = <init>;`
+fn const_decl(name: Ident, init: Expr) -> Decl {
+    Decl::Var(Box::new(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(BindingIdent {
+                id: name,
+                type_ann: None,
+            }),
+            init: Some(Box::new(init)),
+            definite: false,
+        }],
+        ctxt: SyntaxContext::empty(),
+    }))
+}
+
+/// Creates `export { <local> as <exported> };`
+fn export_as(local: Ident, exported: ModuleExportName) -> ModuleDecl {
+    ModuleDecl::ExportNamed(NamedExport {
+        span: DUMMY_SP,
+        specifiers: vec![ExportSpecifier::Named(ExportNamedSpecifier {
+            span: DUMMY_SP,
+            orig: ModuleExportName::Ident(local),
+            exported: Some(exported),
+            is_type_only: false,
+        })],
+        src: None,
+        type_only: false,
+        with: None,
+    })
+}
+
+fn default_module_export_name() -> ModuleExportName {
+    ModuleExportName::Ident(Ident::new("default".into(), DUMMY_SP, Default::default()))
+}
+
+impl TransformVisitor {
+    /// Declares the local mockify binding, depending on the configured `Runtime`.
+    fn runtime_declaration(&self) -> ModuleItem {
+        let export_name = self.config.export_name.as_str();
+        let init = match self.config.runtime {
+            Runtime::Import => {
+                return ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                        span: DUMMY_SP,
+                        local: self.mockify_ident.clone(),
+                        imported: Some(ModuleExportName::Ident(Ident::new(
+                            export_name.into(),
+                            DUMMY_SP,
+                            Default::default(),
+                        ))),
+                        is_type_only: false,
+                    })],
+                    src: Box::new(str_lit(&self.config.import_from)),
+                    type_only: false,
+                    with: None,
+                    phase: ImportPhase::Evaluation,
+                }));
+            }
+            // require("promock").mockify
+            Runtime::Require => member_expr(
+                Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                        "require".into(),
+                        DUMMY_SP,
+                        Default::default(),
+                    )))),
+                    args: vec![ExprOrSpread {
+                        expr: Box::new(Expr::Lit(Lit::Str(str_lit(&self.config.import_from)))),
+                        spread: None,
+                    }],
+                    type_args: None,
+                    ctxt: SyntaxContext::empty(),
+                }),
+                export_name,
+            ),
+            // globalThis.__promock__.mockify
+            Runtime::Global => member_expr(global_runtime_expr(), export_name),
+            // function mockify(value, internalWrapper, metadata) {
+            //   const runtime = globalThis.__promock__;
+            //   return runtime ? runtime.mockify(value, internalWrapper, metadata) : value;
+            // }
+            Runtime::Inline => {
+                let value_ident = self.private_ident("value", DUMMY_SP);
+                let wrapper_ident = self.private_ident("internalWrapper", DUMMY_SP);
+                let metadata_ident = self.private_ident("metadata", DUMMY_SP);
+                let runtime_ident = self.private_ident("runtime", DUMMY_SP);
+                let param = |ident: &Ident| Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat: Pat::Ident(ident.clone().into()),
+                };
+                return ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+                    ident: self.mockify_ident.clone(),
+                    declare: false,
+                    function: Box::new(Function {
+                        span: DUMMY_SP,
+                        params: vec![
+                            param(&value_ident),
+                            param(&wrapper_ident),
+                            param(&metadata_ident),
+                        ],
+                        body: Some(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts: vec![
+                                Stmt::Decl(const_decl(
+                                    runtime_ident.clone(),
+                                    global_runtime_expr(),
+                                )),
+                                Stmt::Return(ReturnStmt {
+                                    span: DUMMY_SP,
+                                    arg: Some(Box::new(Expr::Cond(CondExpr {
+                                        span: DUMMY_SP,
+                                        test: Box::new(Expr::Ident(runtime_ident.clone())),
+                                        cons: Box::new(Expr::Call(CallExpr {
+                                            span: DUMMY_SP,
+                                            callee: Callee::Expr(Box::new(member_expr(
+                                                Expr::Ident(runtime_ident),
+                                                export_name,
+                                            ))),
+                                            args: vec![
+                                                ExprOrSpread {
+                                                    expr: Box::new(Expr::Ident(
+                                                        value_ident.clone(),
+                                                    )),
+                                                    spread: None,
+                                                },
+                                                ExprOrSpread {
+                                                    expr: Box::new(Expr::Ident(wrapper_ident)),
+                                                    spread: None,
+                                                },
+                                                ExprOrSpread {
+                                                    expr: Box::new(Expr::Ident(metadata_ident)),
+                                                    spread: None,
+                                                },
+                                            ],
+                                            type_args: None,
+                                            ctxt: SyntaxContext::empty(),
+                                        })),
+                                        alt: Box::new(Expr::Ident(value_ident)),
+                                    }))),
+                                }),
+                            ],
+                            ctxt: SyntaxContext::empty(),
+                        }),
+                        decorators: vec![],
+                        is_async: false,
+                        is_generator: false,
+                        return_type: None,
+                        type_params: None,
+                        ctxt: SyntaxContext::empty(),
+                    }),
+                })));
+            }
+        };
+        ModuleItem::Stmt(Stmt::Decl(const_decl(self.mockify_ident.clone(), init)))
+    }
+
+    /// Creates an identifier for a binding generated by the transform.
+    /// It's bound to a private syntax context, so it never clashes with the bindings of the module,
+    /// as hygiene renames it when a binding of the same name already exists.
+    fn private_ident(&self, sym: impl Into<JsWord>, span: Span) -> Ident {
+        Ident::new(sym.into(), span, self.private_ctxt)
+    }
+
+    /// Creates a wrapper function that forwards its calls to the proxy of a function,
+    /// so that the references to the function from within the module go through the proxy as well:
+    /// `function <ident>(...args) { return <mockified_ident>.apply(this, args); }`
+    fn forwarding_wrapper(&self, ident: Ident, mockified_ident: &Ident) -> FnDecl {
+        let rest_args_ident = self.private_ident("args", DUMMY_SP);
+        FnDecl {
+            declare: false,
+            ident,
+            function: Box::new(Function {
+                span: DUMMY_SP,
+                params: vec![Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat: Pat::Rest(RestPat {
+                        span: DUMMY_SP,
+                        dot3_token: DUMMY_SP,
+                        arg: Box::new(Pat::Ident(rest_args_ident.clone().into())),
+                        type_ann: None,
+                    }),
+                }],
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: Callee::Expr(Box::new(member_expr(
+                                Expr::Ident(mockified_ident.clone()),
+                                "apply",
+                            ))),
+                            args: vec![
+                                ExprOrSpread {
+                                    expr: Box::new(Expr::This(ThisExpr { span: DUMMY_SP })),
+                                    spread: None,
+                                },
+                                ExprOrSpread {
+                                    expr: Box::new(Expr::Ident(rest_args_ident)),
+                                    spread: None,
+                                },
+                            ],
+                            type_args: None,
+                            ctxt: SyntaxContext::empty(),
+                        }))),
+                    })],
+                    ctxt: SyntaxContext::empty(),
+                }),
+                decorators: vec![],
+                is_async: false,
+                is_generator: false,
+                return_type: None,
+                type_params: None,
+                ctxt: SyntaxContext::empty(),
+            }),
+        }
+    }
+
+    /// Creates `mockify(<expr>, <original_reference>, <metadata>)`,
+    /// where a missing original reference is passed as `void 0`.
+    fn wrap_with_mockify(
+        &mut self,
+        span: Span,
+        expr: Expr,
+        original_reference: Option<Expr>,
+        metadata: ExportMetadata,
+    ) -> Expr {
+        let argument = |expr: Expr| ExprOrSpread {
+            expr: Box::new(expr),
+            spread: None,
+        };
+        let original_reference = original_reference.unwrap_or_else(|| {
+            Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: UnaryOp::Void,
+                arg: Box::new(Expr::Lit(Lit::Num(num_lit(0.0)))),
+            })
+        });
+        let metadata = ExportMetadata {
+            export_name: self.qualified_export_name(&metadata.export_name),
+            ..metadata
+        };
+        if metadata.origin == BindingOrigin::Export {
+            self.register_export(&metadata.export_name, metadata.kind, metadata.span);
+        } else {
+            self.reports.push(ExportReport {
+                export_name: metadata.export_name.clone(),
+                span: metadata.span,
+                decision: ExportDecision::Mockified(metadata.kind, metadata.origin.clone()),
+            });
+        }
+
+        Expr::Call(CallExpr {
+            span,
+            callee: Callee::Expr(Box::new(Expr::Ident(self.mockify_ident.clone()))),
+            args: vec![
+                argument(expr),
+                argument(original_reference),
+                argument(self.metadata_expr(metadata)),
+            ],
+            type_args: None,
+            ctxt: SyntaxContext::empty(),
+        })
+    }
+
+    /// Prefixes the name of a member exported from inside a namespace with the names of its namespaces,
+    /// e.g. `Utils.Nested.fn`
+    fn qualified_export_name(&self, export_name: &str) -> String {
+        let mut qualified_name = self
+            .namespace_path
+            .iter()
+            .map(|namespace| format!("{namespace}."))
+            .collect::<String>();
+        qualified_name.push_str(export_name);
+        qualified_name
+    }
+
+    /// Lists a mockified export in the module registry (see `Config::module_registry`),
+    /// and in the reports (see `Config::explain`).
+    /// Exports of destructuring patterns have no name, and are left out of the module registry.
+    fn register_export(&mut self, export_name: &str, kind: ExportKind, span: Span) {
+        if !export_name.is_empty() {
+            self.registered_exports.push((export_name.into(), kind));
+        }
+        self.reports.push(ExportReport {
+            export_name: export_name.into(),
+            span,
+            decision: ExportDecision::Mockified(kind, BindingOrigin::Export),
+        });
+    }
+
+    /// Reports an export that is left as is (see `Config::explain`)
+    fn report_skipped(&mut self, export_name: &str, span: Span, reason: SkipReason) {
+        self.reports.push(ExportReport {
+            export_name: self.qualified_export_name(export_name),
+            span,
+            decision: ExportDecision::Skipped(reason),
+        });
+    }
+
+    /// Reports what the transform did with the module as warnings, when `Config::explain` is set
+    fn explain(&mut self, module_span: Span) {
+        if !self.config.explain {
+            return;
+        }
+        self.reports.sort_by_key(|report| report.span.lo);
+        HANDLER.with(|handler| {
+            if let Some(reason) = self.module_exclusion {
+                handler
+                    .struct_span_warn(
+                        module_span.shrink_to_lo(),
+                        &format!("promock: this module is not transformed, as {reason}"),
+                    )
+                    .emit();
+                return;
+            }
+            for report in &self.reports {
+                handler
+                    .struct_span_warn(report.span, &report.message())
+                    .emit();
+            }
+        });
+    }
+
+    /// Reports an error for each export that cannot be mockified, when `Config::strict` is set,
+    /// unless it's allowed by `Config::strict_allowlist`
+    fn check_strict(&self) {
+        if !self.config.strict || self.module_exclusion.is_some() {
+            return;
+        }
+        let file_path = self.file_path.as_deref().unwrap_or_default();
+        HANDLER.with(|handler| {
+            for report in &self.reports {
+                let ExportDecision::Skipped(reason) = report.decision else {
+                    continue;
+                };
+                if !reason.is_unmockable() {
+                    continue;
+                }
+                let export_id = format!("{}#{}", file_path, report.export_name);
+                let allowed = self
+                    .config
+                    .strict_allowlist
+                    .iter()
+                    .flatten()
+                    .any(|allowed| allowed.is_match(&export_id));
+                if allowed {
+                    continue;
+                }
+                handler
+                    .struct_span_err(
+                        report.span,
+                        &format!(
+                            "promock: `{}` cannot be mockified, as {}; \
+                             add `{}` to `strictAllowlist` if it's intentional",
+                            report.export_name,
+                            reason.as_str(),
+                            export_id
+                        ),
+                    )
+                    .emit();
+            }
+        });
+    }
+
+    /// Creates `{ path, exportName, kind, line, column }`,
+    /// omitting the path and the location when the source of the module is unknown.
+    fn metadata_expr(&self, metadata: ExportMetadata) -> Expr {
+        let prop = |key: &str, value: Lit| key_value_prop(key, Expr::Lit(value));
+
+        let mut props = vec![];
+        if let Some(file_path) = &self.file_path {
+            props.push(prop("path", Lit::Str(str_lit(file_path))));
+        }
+        props.push(prop("exportName", Lit::Str(str_lit(&metadata.export_name))));
+        props.push(prop("kind", Lit::Str(str_lit(metadata.kind.as_str()))));
+        match &metadata.origin {
+            BindingOrigin::Export => {}
+            BindingOrigin::Rewired => props.push(prop(
+                "rewired",
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: true,
+                }),
+            )),
+            BindingOrigin::Import(imported_from) => {
+                props.push(prop("importedFrom", Lit::Str(str_lit(imported_from))))
+            }
+        }
+        if let Some(source_map) = self.source_map.as_ref() {
+            if !metadata.span.is_dummy() {
+                let loc = source_map.lookup_char_pos(metadata.span.lo);
+                props.push(prop("line", Lit::Num(num_lit(loc.line as f64))));
+                props.push(prop("column", Lit::Num(num_lit((loc.col.0 + 1) as f64))));
+            }
+        }
+
+        Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props,
+        })
+    }
+}
+
+impl TransformVisitor {
+    /// Creates the module registry, listing the mockified exports of the module:
+    /// `const _promock_module = { path, exports: [{ name, kind }] };
+    /// export { _promock_module as __promock_module__ };`
+    fn module_registry(&self) -> Vec<ModuleItem> {
+        let registry_ident = self.private_ident("_promock_module", DUMMY_SP);
+        let exports = self
+            .registered_exports
+            .iter()
+            .map(|(name, kind)| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Object(ObjectLit {
+                        span: DUMMY_SP,
+                        props: vec![
+                            key_value_prop("name", Expr::Lit(Lit::Str(str_lit(name)))),
+                            key_value_prop("kind", Expr::Lit(Lit::Str(str_lit(kind.as_str())))),
+                        ],
+                    })),
+                })
+            })
+            .collect();
+
+        let mut props = vec![];
+        if let Some(file_path) = &self.file_path {
+            props.push(key_value_prop(
+                "path",
+                Expr::Lit(Lit::Str(str_lit(file_path))),
+            ));
+        }
+        props.push(key_value_prop(
+            "exports",
+            Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: exports,
+            }),
+        ));
+
+        vec![
+            ModuleItem::Stmt(Stmt::Decl(const_decl(
+                registry_ident.clone(),
+                Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                }),
+            ))),
+            ModuleItem::ModuleDecl(export_as(
+                registry_ident,
+                ModuleExportName::Ident(Ident::new(
+                    MODULE_REGISTRY_EXPORT.into(),
+                    DUMMY_SP,
+                    Default::default(),
+                )),
+            )),
+        ]
+    }
+}
+
+impl TransformVisitor {
+    /// Returns whether a module-level binding that's not exported is to be mockified (see `Config::rewire`)
+    fn is_rewirable(&self, ident: &Ident) -> bool {
+        self.config.rewire
+            && !self.rewire_exclusions.contains(&ident.to_id())
+            && !self.type_only_identifiers.contains(&ident.to_id())
+    }
+
+    /// Creates the accessor of the rewired bindings, with a getter for each of their proxies:
+    /// `const _promock_rewire = { get <name>() { return <proxy>; } };
+    /// export { _promock_rewire as __promock_rewire__ };`
+    fn rewire_accessor(&self) -> Vec<ModuleItem> {
+        let accessor_ident = self.private_ident("_promock_rewire", DUMMY_SP);
+        let props = self
+            .rewired_bindings
+            .iter()
+            .map(|(name, proxy_ident)| {
+                PropOrSpread::Prop(Box::new(Prop::Getter(GetterProp {
+                    span: DUMMY_SP,
+                    key: PropName::Ident(IdentName::new(name.clone(), DUMMY_SP)),
+                    type_ann: None,
+                    body: Some(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![Stmt::Return(ReturnStmt {
+                            span: DUMMY_SP,
+                            arg: Some(Box::new(Expr::Ident(proxy_ident.clone()))),
+                        })],
+                        ctxt: SyntaxContext::empty(),
+                    }),
+                })))
+            })
+            .collect();
+
+        vec![
+            ModuleItem::Stmt(Stmt::Decl(const_decl(
+                accessor_ident.clone(),
+                Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                }),
+            ))),
+            ModuleItem::ModuleDecl(export_as(
+                accessor_ident,
+                ModuleExportName::Ident(Ident::new(
+                    REWIRE_EXPORT.into(),
+                    DUMMY_SP,
+                    Default::default(),
+                )),
+            )),
+        ]
+    }
+}
+
+impl TransformVisitor {
+    /// Mockifies the named and default imports of the modules matching `Config::mock_imports`,
+    /// and the default imports of JSON modules (see `Config::mock_attribute_imports`),
+    /// by renaming the imported bindings, and declaring their proxies under the original names:
+    /// `import { a as _imported_a } from "pkg"; const a = mockify(_imported_a);`
+    fn mockify_imports(&mut self, import: &mut ImportDecl) {
+        let specifier = import.src.value.to_string();
+        if import.type_only || specifier == self.config.import_from {
+            return;
+        }
+        let matches_mock_imports = self
+            .config
+            .mock_imports
+            .iter()
+            .flatten()
+            .any(|regex| regex.is_match(&specifier));
+        // JSON modules (and others imported with attributes) only have a default export
+        let only_default = !matches_mock_imports
+            && self.config.mock_attribute_imports
+            && (import.with.is_some() || specifier.ends_with(".json"));
+        if !matches_mock_imports && !only_default {
+            return;
+        }
+        let imported_from = resolve_import_specifier(self.file_path.as_deref(), &specifier);
+
+        for import_specifier in &mut import.specifiers {
+            let (local, imported, import_name) = match import_specifier {
+                ImportSpecifier::Named(named) if !named.is_type_only && !only_default => {
+                    let import_name = match &named.imported {
+                        Some(ModuleExportName::Ident(imported)) => imported.sym.to_string(),
+                        Some(ModuleExportName::Str(imported)) => imported.value.to_string(),
+                        None => named.local.sym.to_string(),
+                    };
+                    (&mut named.local, Some(&mut named.imported), import_name)
+                }
+                ImportSpecifier::Default(default) => (&mut default.local, None, "default".into()),
+                // namespace objects are left as is, their members cannot be mockified one by one
+                _ => continue,
+            };
+            // imports of types, or that are only referenced as types, are removed by the compiler
+            if !self.value_references.contains(&local.to_id())
+                || self.type_only_identifiers.contains(&local.to_id())
+            {
+                continue;
+            }
+
+            self.mockify_used = true;
+            let original_local = local.clone();
+            *local = self.private_ident(format!("_imported_{}", original_local.sym), local.span);
+            if let Some(imported @ None) = imported {
+                *imported = Some(ModuleExportName::Ident(Ident::new(
+                    import_name.clone().into(),
+                    DUMMY_SP,
+                    Default::default(),
+                )));
+            }
+            let metadata =
+                ExportMetadata::imported(import_name, imported_from.clone(), original_local.span);
+            let mockified_import =
+                self.wrap_with_mockify(DUMMY_SP, Expr::Ident(local.clone()), None, metadata);
+            self.added_after_imports
+                .push(ModuleItem::Stmt(Stmt::Decl(const_decl(
+                    original_local,
+                    mockified_import,
+                ))));
+        }
+    }
+}
+
+/// Returns the key of an imported module, shared by all the modules importing it:
+/// bare specifiers are kept as they are, while relative ones are resolved
+/// against the path of the importing module (relative to `base_path`), without their extension.
+fn resolve_import_specifier(file_path: Option<&str>, specifier: &str) -> String {
+    let Some(file_path) = file_path else {
+        return specifier.into();
+    };
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return specifier.into();
+    }
+
+    let mut segments: Vec<&str> = file_path.split('/').collect();
+    // the file name of the importing module
+    segments.pop();
+    for segment in specifier.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                if matches!(segments.last(), None | Some(&"..")) {
+                    segments.push("..");
+                } else {
+                    segments.pop();
+                }
+            }
+            _ => segments.push(segment),
+        }
+    }
+    strip_module_extension(&segments.join("/")).into()
+}
+
+/// Collects the bindings referenced as values, i.e. in expressions, exports and JSX,
+/// but not in type annotations.
+#[derive(Default)]
+struct ValueReferenceCollector {
+    references: HashSet<Id>,
+}
+
+impl Visit for ValueReferenceCollector {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Ident(ident) = expr {
+            self.references.insert(ident.to_id());
+        }
+        expr.visit_children_with(self);
+    }
+
+    fn visit_export_named_specifier(&mut self, specifier: &ExportNamedSpecifier) {
+        if let ModuleExportName::Ident(ident) = &specifier.orig {
+            self.references.insert(ident.to_id());
+        }
+    }
+
+    fn visit_jsx_element_name(&mut self, name: &JSXElementName) {
+        if let JSXElementName::Ident(ident) = name {
+            self.references.insert(ident.to_id());
+        }
+        name.visit_children_with(self);
+    }
+
+    fn visit_jsx_object(&mut self, object: &JSXObject) {
+        if let JSXObject::Ident(ident) = object {
+            self.references.insert(ident.to_id());
+        }
+        object.visit_children_with(self);
+    }
+
+    // shorthand properties, e.g. `{ a }`
+    fn visit_prop(&mut self, prop: &Prop) {
+        if let Prop::Shorthand(ident) = prop {
+            self.references.insert(ident.to_id());
+        }
+        prop.visit_children_with(self);
+    }
+
+    fn visit_simple_assign_target(&mut self, target: &SimpleAssignTarget) {
+        if let SimpleAssignTarget::Ident(binding) = target {
+            self.references.insert(binding.id.to_id());
+        }
+        target.visit_children_with(self);
+    }
+}
+
+/// Collects the module-level bindings that are exported by reference (`export { a }`, `export default a`,
+/// `export = a`), and so are mockified as exports, as well as overloaded functions,
+/// none of which are rewired (see `Config::rewire`).
+fn collect_rewire_exclusions(items: &[ModuleItem]) -> HashSet<Id> {
+    let mut exclusions = HashSet::new();
+    for item in items {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src: None,
+                ..
+            })) => {
+                for specifier in specifiers {
+                    if let ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(ident),
+                        ..
+                    }) = specifier
+                    {
+                        exclusions.insert(ident.to_id());
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                expr,
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(TsExportAssignment {
+                expr,
+                ..
+            })) => {
+                if let Expr::Ident(ident) = &**expr {
+                    exclusions.insert(ident.to_id());
+                }
+            }
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) if fn_decl.function.body.is_none() => {
+                exclusions.insert(fn_decl.ident.to_id());
+            }
+            _ => {}
+        }
+    }
+    exclusions
+}
+
+/// Returns the names declared by a declaration, and whether they only exist at the type level.
+fn declared_names(decl: &Decl) -> (Vec<Id>, bool) {
+    match decl {
+        Decl::Class(class_decl) => (vec![class_decl.ident.to_id()], class_decl.declare),
+        Decl::Fn(fn_decl) => (vec![fn_decl.ident.to_id()], fn_decl.declare),
+        Decl::Var(var_decl) => (find_pat_ids(&var_decl.decls), var_decl.declare),
+        Decl::TsInterface(interface_decl) => (vec![interface_decl.id.to_id()], true),
+        Decl::TsTypeAlias(type_alias_decl) => (vec![type_alias_decl.id.to_id()], true),
+        // const enums are inlined by the compiler, so there's nothing to reference at runtime
+        Decl::TsEnum(enum_decl) => (
+            vec![enum_decl.id.to_id()],
+            enum_decl.declare || enum_decl.is_const,
+        ),
+        Decl::TsModule(module_decl) => match &module_decl.id {
+            TsModuleName::Ident(ident) => (
+                vec![ident.to_id()],
+                module_decl.declare || !is_namespace_instantiated(module_decl.body.as_ref()),
+            ),
+            TsModuleName::Str(_) => (vec![], true),
+        },
+        Decl::Using(using_decl) => (find_pat_ids(&using_decl.decls), false),
+    }
+}
+
+/// Returns whether a namespace has a runtime value,
+/// i.e. it contains something else than types and ambient declarations.
+fn is_namespace_instantiated(body: Option<&TsNamespaceBody>) -> bool {
+    match body {
+        None => false,
+        Some(TsNamespaceBody::TsNamespaceDecl(nested)) => {
+            !nested.declare && is_namespace_instantiated(Some(&nested.body))
+        }
+        Some(TsNamespaceBody::TsModuleBlock(block)) => block.body.iter().any(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                !declared_names(&export.decl).1
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => !import.is_type_only,
+            ModuleItem::ModuleDecl(_) => false,
+            ModuleItem::Stmt(Stmt::Decl(decl)) => !declared_names(decl).1,
+            ModuleItem::Stmt(Stmt::Empty(_)) => false,
+            ModuleItem::Stmt(_) => true,
+        }),
+    }
+}
+
+/// Collects the module-level bindings that only exist at the type level,
+/// i.e. interfaces, type aliases, ambient (`declare`) declarations, const enums and type-only imports.
+/// Names that are also declared as a value (e.g. an interface merged with a class) are not included.
+fn collect_type_only_identifiers(items: &[ModuleItem]) -> HashSet<Id> {
+    let mut type_names = HashSet::new();
+    let mut value_names = HashSet::new();
+
+    for item in items {
+        let (names, is_type_only) = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                for specifier in &import.specifiers {
+                    let (local, is_type_only) = match specifier {
+                        ImportSpecifier::Named(named) => {
+                            (&named.local, import.type_only || named.is_type_only)
+                        }
+                        ImportSpecifier::Default(default) => (&default.local, import.type_only),
+                        ImportSpecifier::Namespace(namespace) => {
+                            (&namespace.local, import.type_only)
+                        }
+                    };
+                    if is_type_only {
+                        type_names.insert(local.to_id());
+                    } else {
+                        value_names.insert(local.to_id());
+                    }
+                }
+                continue;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => {
+                (vec![import.id.to_id()], import.is_type_only)
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => declared_names(&export.decl),
+            ModuleItem::Stmt(Stmt::Decl(decl)) => declared_names(decl),
+            _ => continue,
+        };
+        if is_type_only {
+            type_names.extend(names);
+        } else {
+            value_names.extend(names);
+        }
+    }
+
+    type_names.retain(|name| !value_names.contains(name));
+    type_names
+}
+
+/// Collects the module-level functions that are declared with a body, exported or not,
+/// including `export default function <name>() {}`.
+fn collect_function_declarations(items: &[ModuleItem]) -> HashSet<Id> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl)))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Fn(fn_decl),
+                ..
+            })) if !fn_decl.declare && fn_decl.function.body.is_some() => {
+                Some(fn_decl.ident.to_id())
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::Fn(fn_expr),
+                ..
+            })) if fn_expr.function.body.is_some() => fn_expr.ident.as_ref().map(Ident::to_id),
+            _ => None,
+        })
+        .collect()
+}
+
+impl TransformVisitor {
+    /// Mockifies the exports of the module (see `visit_mut_module`, which verifies its exports around it)
+    fn transform_module(&mut self, m: &mut Module) {
+        self.private_ctxt = SyntaxContext::empty().apply_mark(Mark::new());
+        self.mockify_ident = self.private_ident(self.config.import_as.clone(), DUMMY_SP);
+        let file_path = self.file_path.as_deref().unwrap_or_default();
+        if self.module_exclusion.is_none()
+            && imports_itself(&self.config.base_path, &self.config.import_from, file_path)
+        {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_warn(
+                        m.span.shrink_to_lo(),
+                        &format!(
+                            "promock: \"{}\" resolves to this module, which would import `{}` from itself \
+                             once mockified, so it's left untransformed",
+                            self.config.import_from, self.config.export_name
+                        ),
+                    )
+                    .emit()
+            });
+            self.module_exclusion = Some("it's the runtime mockify is imported from");
+        }
+        if self.module_exclusion.is_some() {
+            self.explain(m.span);
+            return;
+        }
+        self.type_only_identifiers = collect_type_only_identifiers(&m.body);
+        self.function_declarations = collect_function_declarations(&m.body);
+        if self.config.rewire {
+            self.rewire_exclusions = collect_rewire_exclusions(&m.body);
+        }
+        if self.config.mock_imports.is_some() || self.config.mock_attribute_imports {
+            let mut collector = ValueReferenceCollector::default();
+            m.visit_with(&mut collector);
+            self.value_references = collector.references;
+        }
+        self.register_exported_declarations(&m.body);
+        m.visit_mut_children_with(self);
+        self.explain(m.span);
+        self.check_strict();
+
+        if self.do_not_mockify {
+            return;
+        }
+        if !self.mockify_used {
+            return;
+        }
+
+        // drop the items that were taken out of their original place (e.g. overload signatures)
+        m.body
+            .retain(|item| !matches!(item, ModuleItem::Stmt(Stmt::Empty(..))));
+
+        // the proxies of imports and functions go right after the imports
+        let after_imports = m
+            .body
+            .iter()
+            .rposition(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(..))))
+            .map_or(0, |index| index + 1);
+        let after_imports_items: Vec<ModuleItem> = self.added_after_imports.drain(..).collect();
+        m.body
+            .splice(after_imports..after_imports, after_imports_items);
+
+        // Prepend our stored statements
+        let prepend_items: Vec<ModuleItem> = self.added_to_top_of_file.drain(..).collect();
+        m.body.splice(0..0, prepend_items);
+        let append_items: Vec<ModuleItem> = self.added_to_bottom_of_file.drain(..).collect();
+        m.body.splice(m.body.len()..m.body.len(), append_items);
+        if self.config.module_registry && !self.has_export_assignment {
+            m.body.extend(self.module_registry());
+        }
+        if !self.rewired_bindings.is_empty() && !self.has_export_assignment {
+            m.body.extend(self.rewire_accessor());
+        }
+
+        // Prepend the mockify import
+        m.body.insert(0, self.runtime_declaration());
+
+        // Mark the module as transformed, so that it's left as is if it goes through the plugin again
+        m.body.insert(
+            0,
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                    value: MOCKIFIED_DIRECTIVE.into(),
+                    span: DUMMY_SP,
+                    raw: None,
+                }))),
+            })),
+        );
+    }
+
+    /// Reports an error when the transform changed the exports of the module (see `Config::verify_exports`)
+    fn verify_exports(
+        &self,
+        module_span: Span,
+        before: BTreeSet<ExportShape>,
+        after: BTreeSet<ExportShape>,
+    ) {
+        let after = after
+            .into_iter()
+            .filter(|export| !matches!(export, ExportShape::Value(name) if name == MODULE_REGISTRY_EXPORT || name == REWIRE_EXPORT))
+            .collect::<BTreeSet<_>>();
+        if before == after {
+            return;
+        }
+
+        let list = |exports: Vec<&ExportShape>| {
+            exports
+                .iter()
+                .map(|export| export.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let missing = list(before.difference(&after).collect());
+        let added = list(after.difference(&before).collect());
+        let message = format!(
+            "promock: the transform changed the exports of this module, which is a bug of promock \
+             (missing: [{missing}], added: [{added}])"
+        );
+        HANDLER.with(|handler| {
+            handler
+                .struct_span_err(module_span.shrink_to_lo(), &message)
+                .emit()
+        });
+    }
+}
+
+impl VisitMut for TransformVisitor {
+    fn visit_mut_module(&mut self, m: &mut Module) {
+        let exports_before = self.config.verify_exports.then(|| export_shape(&m.body));
+        self.transform_module(m);
+        if let Some(exports_before) = exports_before {
+            self.verify_exports(m.span, exports_before, export_shape(&m.body));
+        }
+    }
+
+    fn visit_mut_expr_stmt(&mut self, n: &mut ExprStmt) {
+        if let Expr::Lit(Lit::Str(str_lit)) = &*n.expr {
+            if str_lit.value.eq(DO_NOT_MOCKIFY_DIRECTIVE) {
+                self.do_not_mockify = true;
+                self.module_exclusion = Some("it has a \"use __do_not_mockify__\" directive");
+            } else if str_lit.value.eq(MOCKIFIED_DIRECTIVE) {
+                self.do_not_mockify = true;
+                self.module_exclusion = Some("it's already mockified");
+            }
+        }
+    }
+    // Implement necessary visit_mut_* methods for actual custom transform.
+    // A comprehensive list of possible visitor methods can be found here:
+    // https://rustdoc.swc.rs/swc_ecma_visit/trait.VisitMut.html
+    fn visit_mut_module_item(&mut self, item: &mut ModuleItem) {
+        if self.do_not_mockify {
+            return;
+        }
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export))
+                if matches!(&*export.expr, Expr::Ident(ident)
+                    if !self.type_only_identifiers.contains(&ident.to_id())) =>
+            {
+                let Expr::Ident(ident) = &*export.expr else {
+                    unreachable!()
+                };
+                *item = self.mockify_default_export_of_binding(&ident.clone(), export.span);
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                let export_span = export.span;
+                for (export_name, span, reason) in skipped_declarations(&export.decl) {
+                    self.report_skipped(&export_name, span, reason);
+                }
+                match &mut export.decl {
+                    Decl::Var(var_decl) if var_decl.kind == VarDeclKind::Const => {
+                        if var_decl.declare {
+                            return;
+                        }
+                        for decl in &mut var_decl.decls {
+                            // destructured bindings are reported as skipped (see `skipped_declarations`)
+                            if let (Pat::Ident(binding), Some(init)) = (&decl.name, &mut decl.init)
+                            {
+                                self.mockify_used = true;
+                                let metadata = ExportMetadata::new(
+                                    binding.id.sym.to_string(),
+                                    ExportKind::Const,
+                                    decl.span,
+                                );
+                                **init = self.wrap_with_mockify(
+                                    decl.span,
+                                    *(*init).take(),
+                                    None,
+                                    metadata,
+                                );
+                            }
+                        }
+                    }
+                    Decl::Fn(fn_decl) => {
+                        if fn_decl.declare {
+                            return;
+                        }
+                        // TypeScript overload signatures have no body,
+                        // they are renamed along with the implementation that follows them,
+                        // and copied over to the wrapper, so that only the implementation is mockified
+                        if fn_decl.function.body.is_none() {
+                            self.overload_signatures.push(FnDecl {
+                                ident: fn_decl.ident.clone(),
+                                function: fn_decl.function.clone(),
+                                declare: false,
+                            });
+                            *item = ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+                                ident: self.private_ident(
+                                    format!("_actual_{}", fn_decl.ident.sym),
+                                    fn_decl.ident.span,
+                                ),
+                                function: fn_decl.function.clone(),
+                                declare: false,
+                            })));
+                            return;
+                        }
+                        self.mockify_used = true;
+                        let orig_ident = fn_decl.ident.clone();
+                        let export_ident = fn_decl.ident.clone();
+                        let mockified_ident =
+                            match self.mockified_identifiers.get(&orig_ident.to_id()) {
+                                Some(mockified_ident) => mockified_ident.clone(),
+                                None => self.private_ident(
+                                    format!("_mockified_{}", orig_ident.sym),
+                                    orig_ident.span,
+                                ),
+                            };
+
+                        // Rename original function to `_actual_<name>`
+                        let renamed_ident = self
+                            .private_ident(format!("_actual_{}", orig_ident.sym), orig_ident.span);
+
+                        // Drop the export, but keep the original function declaration
+                        let renamed_fn_decl = FnDecl {
+                            ident: renamed_ident.clone(),
+                            function: fn_decl.function.clone(),
+                            declare: false,
+                        };
+                        let renamed_fn_decl_module_item =
+                            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(renamed_fn_decl)));
+                        *item = renamed_fn_decl_module_item;
+
+                        let wrapper_fn_decl =
+                            self.forwarding_wrapper(orig_ident.clone(), &mockified_ident);
+                        // keep the overload signatures in front of the wrapper,
+                        // so that the exported function retains its overloaded type
+                        let overload_signatures: Vec<FnDecl> =
+                            self.overload_signatures.drain(..).collect();
+                        for signature in overload_signatures {
+                            if signature.ident.to_id() == orig_ident.to_id() {
+                                self.added_to_bottom_of_file
+                                    .push(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(signature))));
+                            }
+                        }
+                        self.added_to_bottom_of_file
+                            .push(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(wrapper_fn_decl))));
+
+                        // Create mockified version:
+                        // mockify(_actual_fn);
+                        let mockified_fn = self.wrap_with_mockify(
+                            DUMMY_SP,
+                            Expr::Ident(renamed_ident.clone()),
+                            Some(Expr::Ident(orig_ident.clone())),
+                            ExportMetadata::new(
+                                orig_ident.sym.to_string(),
+                                ExportKind::Function,
+                                export_span,
+                            ),
+                        );
+                        // create const declaration for mockified version, right after the imports,
+                        // so that the module can call the function while it's evaluated:
+                        // const _mockified_fn = mockify(_actual_fn);
+                        let mockified_fn_const = VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(BindingIdent {
+                                id: mockified_ident.clone(),
+                                type_ann: None,
+                            }),
+                            init: Some(Box::new(mockified_fn)),
+                            definite: false,
+                        };
+                        self.added_after_imports
+                            .push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                                span: DUMMY_SP,
+                                kind: VarDeclKind::Const,
+                                declare: false,
+                                decls: vec![mockified_fn_const],
+                                ctxt: SyntaxContext::empty(),
+                            })))));
+
+                        // Add the original and mockified declarations to our stored items
+                        // self.added_to_top_of_file.push(renamed_fn_decl_module_item);
+
+                        // Export the mockified version under the original exported name
+                        // export { __mockified__fn as fn };
+                        let mockified_const_declaration = ModuleItem::ModuleDecl(export_as(
+                            mockified_ident.clone(),
+                            ModuleExportName::Ident(export_ident),
+                        ));
+
+                        self.added_to_bottom_of_file
+                            .push(mockified_const_declaration);
+
+                        // let function_declaration_const =
+                        //     ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                        //         span: fn_decl.function.span,
+                        //         kind: VarDeclKind::Const,
+                        //         declare: false,
+                        //         decls: vec![mockified_fn_decl],
+                        //     }))));
+
+                        // *item = function_declaration_const;
+                    }
+                    Decl::TsEnum(enum_decl) if !enum_decl.declare && !enum_decl.is_const => {
+                        let ident = enum_decl.id.clone();
+                        let decl = export.decl.clone();
+                        self.mockify_exported_declaration(
+                            item,
+                            ident,
+                            decl,
+                            ExportKind::Enum,
+                            export_span,
+                        );
+                    }
+                    Decl::TsModule(module_decl)
+                        if !module_decl.declare
+                            && is_namespace_instantiated(module_decl.body.as_ref()) =>
+                    {
+                        let TsModuleName::Ident(ident) = module_decl.id.clone() else {
+                            return;
+                        };
+                        // mockify the functions and consts exported from inside the namespace
+                        module_decl.visit_mut_with(self);
+                        let decl = export.decl.clone();
+                        self.mockify_exported_declaration(
+                            item,
+                            ident,
+                            decl,
+                            ExportKind::Namespace,
+                            export_span,
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            // overload signatures of a default exported function need to stay
+            // right before its implementation, which is moved to the top of the file
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::Fn(fn_expr),
+                ..
+            })) if fn_expr.function.body.is_none() => {
+                if let Some(ident) = &fn_expr.ident {
+                    self.added_to_top_of_file
+                        .push(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+                            ident: ident.clone(),
+                            function: fn_expr.function.clone(),
+                            declare: false,
+                        }))));
+                }
+                item.take();
+            }
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl)))
+                if !fn_decl.declare
+                    && fn_decl.function.body.is_some()
+                    && self.is_rewirable(&fn_decl.ident) =>
+            {
+                // the same as exported functions, minus the export
+                self.mockify_used = true;
+                let ident = fn_decl.ident.clone();
+                let mockified_ident =
+                    self.private_ident(format!("_mockified_{}", ident.sym), ident.span);
+                let renamed_ident =
+                    self.private_ident(format!("_actual_{}", ident.sym), ident.span);
+                let span = fn_decl.function.span;
+                *item = ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+                    ident: renamed_ident.clone(),
+                    function: fn_decl.function.clone(),
+                    declare: false,
+                })));
+
+                let wrapper_fn_decl = self.forwarding_wrapper(ident.clone(), &mockified_ident);
+                self.added_to_bottom_of_file
+                    .push(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(wrapper_fn_decl))));
+                let mockified_fn = self.wrap_with_mockify(
+                    DUMMY_SP,
+                    Expr::Ident(renamed_ident),
+                    Some(Expr::Ident(ident.clone())),
+                    ExportMetadata::rewired(ident.sym.to_string(), ExportKind::Function, span),
+                );
+                self.added_after_imports
+                    .push(ModuleItem::Stmt(Stmt::Decl(const_decl(
+                        mockified_ident.clone(),
+                        mockified_fn,
+                    ))));
+                self.rewired_bindings.push((ident.sym, mockified_ident));
+            }
+            // classes are not hoisted, so they can be replaced in place:
+            // `const <name> = mockify(class <name> {});`
+            ModuleItem::Stmt(Stmt::Decl(Decl::Class(class_decl)))
+                if !class_decl.declare && self.is_rewirable(&class_decl.ident) =>
+            {
+                self.mockify_used = true;
+                let ident = class_decl.ident.clone();
+                let metadata = ExportMetadata::rewired(
+                    ident.sym.to_string(),
+                    ExportKind::Class,
+                    class_decl.class.span,
+                );
+                let class_expr = Expr::Class(ClassExpr {
+                    ident: Some(ident.clone()),
+                    class: class_decl.class.take(),
+                });
+                let mockified_class = self.wrap_with_mockify(DUMMY_SP, class_expr, None, metadata);
+                *item = ModuleItem::Stmt(Stmt::Decl(const_decl(ident.clone(), mockified_class)));
+                self.rewired_bindings.push((ident.sym.clone(), ident));
+            }
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl)))
+                if self.config.rewire
+                    && var_decl.kind == VarDeclKind::Const
+                    && !var_decl.declare =>
+            {
+                for decl in &mut var_decl.decls {
+                    let (Pat::Ident(binding), Some(init)) = (&decl.name, &mut decl.init) else {
+                        continue;
+                    };
+                    if !self.is_rewirable(&binding.id) {
+                        continue;
+                    }
+                    self.mockify_used = true;
+                    let metadata = ExportMetadata::rewired(
+                        binding.id.sym.to_string(),
+                        ExportKind::Const,
+                        decl.span,
+                    );
+                    **init = self.wrap_with_mockify(decl.span, *init.take(), None, metadata);
+                    self.rewired_bindings
+                        .push((binding.id.sym.clone(), binding.id.clone()));
+                }
+            }
+            _ => item.visit_mut_children_with(self),
+        }
+    }
+
+    fn visit_mut_ts_module_decl(&mut self, module_decl: &mut TsModuleDecl) {
+        let TsModuleName::Ident(ident) = &module_decl.id else {
+            return;
+        };
+        self.namespace_path.push(ident.sym.clone());
+        module_decl.visit_mut_children_with(self);
+        self.namespace_path.pop();
+    }
+
+    // `namespace A.B {}`
+    fn visit_mut_ts_namespace_decl(&mut self, namespace_decl: &mut TsNamespaceDecl) {
+        self.namespace_path.push(namespace_decl.id.sym.clone());
+        namespace_decl.visit_mut_children_with(self);
+        self.namespace_path.pop();
+    }
+
+    // Members exported from inside a namespace become properties of the namespace object,
+    // and internal references to them are compiled to property accesses on it,
+    // so they can be wrapped in place, without the need for a wrapper function:
+    // `export function fn() {}` becomes
+    // `export const fn = mockify(_actual_fn); function _actual_fn() {}`
+    fn visit_mut_ts_module_block(&mut self, block: &mut TsModuleBlock) {
+        let mut mockified_functions = vec![];
+        for item in &mut block.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) = item else {
+                continue;
+            };
+            let export_span = export.span;
+            for (export_name, span, reason) in skipped_declarations(&export.decl) {
+                self.report_skipped(&export_name, span, reason);
+            }
+            match &mut export.decl {
+                Decl::Var(var_decl) if var_decl.kind == VarDeclKind::Const && !var_decl.declare => {
+                    for decl in &mut var_decl.decls {
+                        if let (Pat::Ident(binding), Some(init)) = (&decl.name, &mut decl.init) {
+                            self.mockify_used = true;
+                            let metadata = ExportMetadata::new(
+                                binding.id.sym.to_string(),
+                                ExportKind::Const,
+                                decl.span,
+                            );
+                            **init =
+                                self.wrap_with_mockify(decl.span, *(*init).take(), None, metadata);
+                        }
+                    }
+                }
+                Decl::Fn(fn_decl) if !fn_decl.declare => {
+                    let renamed_ident = self.private_ident(
+                        format!("_actual_{}", fn_decl.ident.sym),
+                        fn_decl.ident.span,
+                    );
+                    // overload signatures are renamed along with their implementation
+                    if fn_decl.function.body.is_some() {
+                        self.mockify_used = true;
+                        mockified_functions.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(
+                            ExportDecl {
+                                span: DUMMY_SP,
+                                decl: Decl::Var(Box::new(VarDecl {
+                                    span: DUMMY_SP,
+                                    kind: VarDeclKind::Const,
+                                    declare: false,
+                                    decls: vec![VarDeclarator {
+                                        span: DUMMY_SP,
+                                        name: Pat::Ident(BindingIdent {
+                                            id: fn_decl.ident.clone(),
+                                            type_ann: None,
+                                        }),
+                                        init: Some(Box::new(self.wrap_with_mockify(
+                                            DUMMY_SP,
+                                            Expr::Ident(renamed_ident.clone()),
+                                            None,
+                                            ExportMetadata::new(
+                                                fn_decl.ident.sym.to_string(),
+                                                ExportKind::Function,
+                                                export_span,
+                                            ),
+                                        ))),
+                                        definite: false,
+                                    }],
+                                    ctxt: SyntaxContext::empty(),
+                                })),
+                            },
+                        )));
+                    }
+                    *item = ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+                        ident: renamed_ident,
+                        function: fn_decl.function.clone(),
+                        declare: false,
+                    })));
+                }
+                Decl::TsModule(module_decl) if !module_decl.declare => {
+                    module_decl.visit_mut_with(self);
+                }
+                _ => {}
+            }
+        }
+        // function declarations are hoisted, so their mockified versions go first
+        block.body.splice(0..0, mockified_functions);
+
+        for item in &mut block.body {
+            if let ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(module_decl))) = item {
+                if !module_decl.declare {
+                    module_decl.visit_mut_with(self);
+                }
+            }
+        }
+    }
+
+    fn visit_mut_module_decl(&mut self, item: &mut ModuleDecl) {
+        if self.do_not_mockify {
+            return;
+        }
+        match item {
+            ModuleDecl::Import(import) => self.mockify_imports(import),
+            ModuleDecl::ExportNamed(named_export) => {
+                // This flag will help us know if we processed any identifiers for mockifying
+                let mut mockified_any = false;
+
+                if named_export.src.is_some() {
+                    for specifier in &named_export.specifiers {
+                        let (export_name, span) = specifier_export_name(specifier);
+                        self.report_skipped(&export_name, span, SkipReason::ReExport);
+                    }
+                    return;
+                }
+
+                // `export type { A }` has no runtime representation
+                if named_export.type_only {
+                    for specifier in &named_export.specifiers {
+                        let (export_name, span) = specifier_export_name(specifier);
+                        self.report_skipped(&export_name, span, SkipReason::TypeOnly);
+                    }
+                    return;
+                }
+
+                // For each specifier, mockify its source identifier and adjust the exported name
+                let mut new_specifiers = vec![];
+                for specifier in &named_export.specifiers {
+                    match specifier {
+                        ExportSpecifier::Named(named_specifier) => {
+                            // The original identifier being exported
+                            let original_ident = &named_specifier.orig;
+                            let original_export_as = &named_specifier.exported;
+
+                            // without `from`, only identifiers can be exported,
+                            // but they can be exported under any string name (`export { a as "a-b" }`),
+                            // which is kept as is, since the generated bindings are named after the local one
+                            let ModuleExportName::Ident(original_ident) = original_ident else {
+                                new_specifiers.push(specifier.clone());
+                                continue;
+                            };
+
+                            // `export { type A }`, or exporting an interface, type alias, etc.
+                            if named_specifier.is_type_only
+                                || self.type_only_identifiers.contains(&original_ident.to_id())
+                            {
+                                let (export_name, span) = specifier_export_name(specifier);
+                                self.report_skipped(&export_name, span, SkipReason::TypeOnly);
+                                new_specifiers.push(specifier.clone());
+                                continue;
+                            }
+
+                            // Get the mockified name, e.g., _mockified_A
+                            let export_name = match original_export_as {
+                                Some(ModuleExportName::Ident(exported)) => exported.sym.to_string(),
+                                Some(ModuleExportName::Str(exported)) => exported.value.to_string(),
+                                None => original_ident.sym.to_string(),
+                            };
+                            let metadata = ExportMetadata::new(
+                                export_name.clone(),
+                                ExportKind::Binding,
+                                named_specifier.span,
+                            );
+                            let (mockified_ident, created) =
+                                self.mockify_binding(original_ident, metadata);
+                            if !created {
+                                self.register_export(
+                                    &export_name,
+                                    ExportKind::Binding,
+                                    named_specifier.span,
+                                );
+                            }
+
+                            // the binding itself holds the proxy (e.g. `export const`)
+                            if mockified_ident.to_id() == original_ident.to_id() {
+                                new_specifiers.push(specifier.clone());
+                                continue;
+                            }
+                            mockified_any = true;
+
+                            // Create a new named export specifier using the mockified name
+                            new_specifiers.push(ExportSpecifier::Named(ExportNamedSpecifier {
+                                span: DUMMY_SP,
+                                orig: mockified_ident.into(),
+                                exported: match original_export_as {
+                                    None => Some(ModuleExportName::Ident(original_ident.clone())),
+                                    _ => original_export_as.clone(),
+                                },
+                                is_type_only: false,
+                            }));
+                        }
+                        _ => {
+                            new_specifiers.push(specifier.clone());
+                        }
+                    }
+                }
+
+                // If we mockified any identifiers, we'll adjust the named export
+                if mockified_any {
+                    *item = ModuleDecl::ExportNamed(NamedExport {
+                        span: named_export.span,
+                        specifiers: new_specifiers,
+                        src: None,
+                        type_only: named_export.type_only,
+                        with: named_export.with.clone(),
+                    });
+                }
+            }
+
+            // `export = A` is the CommonJS-style equivalent of a default export
+            ModuleDecl::TsExportAssignment(export) => {
+                if let Expr::Ident(ident) = &*export.expr {
+                    if self.type_only_identifiers.contains(&ident.to_id()) {
+                        self.report_skipped("export=", export.span, SkipReason::TypeOnly);
+                        return;
+                    }
+                }
+                self.mockify_used = true;
+                self.has_export_assignment = true;
+                let metadata =
+                    ExportMetadata::new("export=", ExportKind::ExportAssignment, export.span);
+                *export.expr =
+                    self.wrap_with_mockify(export.span, *export.expr.take(), None, metadata);
+            }
+
+            ModuleDecl::ExportDefaultExpr(export) => {
+                // `export default A`, where A is an interface, type alias, etc.
+                if let Expr::Ident(ident) = &*export.expr {
+                    if self.type_only_identifiers.contains(&ident.to_id()) {
+                        self.report_skipped("default", export.span, SkipReason::TypeOnly);
+                        return;
+                    }
+                    // handled by `visit_mut_module_item`, as its proxy takes the place of the export
+                    return;
+                }
+                self.mockify_used = true;
+                let metadata = ExportMetadata::new("default", ExportKind::Expression, export.span);
+                *export.expr =
+                    self.wrap_with_mockify(export.span, *export.expr.clone(), None, metadata);
+            }
+
+            // we cannot simply replace the function with a const,
+            // because that would remove the identifier from scope
+            // which may cause a ReferenceError in runtime
+            // we need to drop the 'export default' from the original declaration,
+            // then export its proxy instead: 'export { _mockified_$identifier as default }'
+            ModuleDecl::ExportDefaultDecl(export) => match &export.decl {
+                DefaultDecl::Fn(fn_expr) => {
+                    self.mockify_used = true;
+
+                    // handle case where function ident doesn't exist
+                    // in which case we can simply wrap the expression directly
+                    if fn_expr.ident.is_none() {
+                        let wrapped_expr = self.wrap_with_mockify(
+                            fn_expr.function.span,
+                            Expr::Fn(fn_expr.clone()),
+                            None,
+                            ExportMetadata::new("default", ExportKind::Function, export.span),
+                        );
+
+                        // Replace the exported default function declaration with a wrapped expression
+                        *item = ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                            span: export.span,
+                            expr: Box::new(wrapped_expr),
+                        });
+                        return;
+                    }
+
+                    // Add the original declaration without the 'export default'
+                    // we can safely unwrap here because we know the ident exists
+                    let original_ident = fn_expr.ident.clone().unwrap();
+                    let ident = original_ident.clone();
+                    let original_stmt = Stmt::Decl(Decl::Fn(FnDecl {
+                        ident: original_ident,
+                        function: Box::new((*fn_expr.function).clone()),
+                        declare: false,
+                    }));
+
+                    self.added_to_top_of_file
+                        .push(ModuleItem::Stmt(original_stmt));
+
+                    // Replace the exported default function declaration with its proxy
+                    let metadata =
+                        ExportMetadata::new("default", ExportKind::Function, export.span);
+                    let (mockified_ident, created) = self.mockify_binding(&ident, metadata);
+                    if !created {
+                        self.register_export("default", ExportKind::Function, export.span);
+                    }
+                    *item = export_as(mockified_ident, default_module_export_name());
+                }
+                DefaultDecl::Class(class_expr) => {
+                    self.mockify_used = true;
+
+                    // handle case where class ident doesn't exist
+                    // in which case we can simply wrap the expression directly
+                    if class_expr.ident.is_none() {
+                        let wrapped_expr = self.wrap_with_mockify(
+                            class_expr.class.span,
+                            Expr::Class(class_expr.clone()),
+                            None,
+                            ExportMetadata::new("default", ExportKind::Class, export.span),
+                        );
+
+                        // Replace the exported default class declaration with a wrapped expression
+                        *item = ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                            span: export.span,
+                            expr: Box::new(wrapped_expr),
+                        });
+                        return;
+                    }
+
+                    // Add the original declaration without the 'export default'
+                    // we can safely unwrap here because we know the ident exists
+                    let original_ident = class_expr.ident.clone().unwrap();
+                    let ident = original_ident.clone();
+                    let original_stmt = Stmt::Decl(Decl::Class(ClassDecl {
+                        ident: original_ident,
+                        class: Box::new((*class_expr.class).clone()),
+                        declare: false,
+                    }));
+
+                    self.added_to_top_of_file
+                        .push(ModuleItem::Stmt(original_stmt));
+
+                    // Replace the exported default class declaration with its proxy
+                    let metadata = ExportMetadata::new("default", ExportKind::Class, export.span);
+                    let (mockified_ident, created) = self.mockify_binding(&ident, metadata);
+                    if !created {
+                        self.register_export("default", ExportKind::Class, export.span);
+                    }
+                    *item = export_as(mockified_ident, default_module_export_name());
+                }
+                DefaultDecl::TsInterfaceDecl(..) => {
+                    self.report_skipped("default", export.span, SkipReason::TypeOnly);
+                }
+            },
+            ModuleDecl::ExportAll(export) => {
+                self.report_skipped("*", export.span, SkipReason::ReExport);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Creates the transform of the module at `filename`, mockifying its exports.
+/// The module is left untouched when it's not included by the paths of the config, or when it's the runtime itself.
+///
+/// Like the `TransformVisitor`, it expects a resolved module.
+pub fn promock_transform(config: Config, filename: &str) -> impl Fold + VisitMut {
+    transform(config, filename, None)
+}
+
+/// The same as `promock_transform`, also reporting the line and column of the exports in their metadata.
+pub fn promock_transform_with_source_map(
+    config: Config,
+    filename: &str,
+    source_map: Lrc<dyn SourceMapper>,
+) -> impl Fold + VisitMut {
+    transform(config, filename, Some(source_map))
+}
+
+fn transform(
+    config: Config,
+    filename: &str,
+    source_map: Option<Lrc<dyn SourceMapper>>,
+) -> Optional<Folder<TransformVisitor>> {
+    let relative_path = relative_posix_path(&config.base_path, filename);
+    let config = config.for_path(&relative_path);
+    let module_exclusion = config.path_exclusion(&relative_path);
+    // excluded modules still go through the visitor when explaining, for it to report why they're left as is
+    let enabled = module_exclusion.is_none() || config.explain;
+
+    let mut visitor = TransformVisitor::new(Some(config));
+    visitor.module_exclusion = module_exclusion;
+    visitor.file_path = Some(relative_path);
+    visitor.source_map = source_map;
+    Optional::new(as_folder(visitor), enabled)
+}
+
+impl Config {
+    /// Returns the config of the module at `relative_path` (relative to `base_path`),
+    /// with the `overrides` matching its path applied.
+    pub fn for_path(&self, relative_path: &str) -> Config {
+        let mut config = self.clone();
+        for config_override in &self.overrides {
+            if config_override
+                .paths
+                .iter()
+                .any(|path| path.is_match(relative_path))
+            {
+                config_override.apply(&mut config);
+            }
+        }
+        config
+    }
+
+    /// Returns whether the module at `relative_path` (relative to `base_path`) is to be transformed,
+    /// according to `include_paths` and `exclude_paths`.
+    /// The runtime itself is never transformed, so that it doesn't import mockify from itself.
+    pub fn is_path_included(&self, relative_path: &str) -> bool {
+        self.path_exclusion(relative_path).is_none()
+    }
+
+    /// Returns why the module at `relative_path` is not transformed, if it isn't (see `is_path_included`).
+    pub fn path_exclusion(&self, relative_path: &str) -> Option<&'static str> {
+        // If include_paths is defined, only include files that match the regex
+        if let Some(include_paths) = &self.include_paths {
+            if !include_paths
+                .iter()
+                .any(|include_path| include_path.is_match(relative_path))
+            {
+                return Some("its path doesn't match `includePaths`");
+            }
+        }
+
+        // If exclude_paths is defined, exclude files that match the regex
+        if let Some(exclude_paths) = &self.exclude_paths {
+            if exclude_paths
+                .iter()
+                .any(|exclude_path| exclude_path.is_match(relative_path))
+            {
+                return Some("its path matches `excludePaths`");
+            }
+        }
+
+        is_runtime_module(&self.import_from, relative_path)
+            .then_some("it's the runtime mockify is imported from")
+    }
+
+    /// Returns whether modules are transformed in the environment of the host,
+    /// e.g. the environment name of swc (see `envs`).
+    pub fn is_enabled_in_env(&self, env: Option<&str>) -> bool {
+        is_enabled_in_env(self.envs.as_deref(), env)
+    }
+}
+
+/// Returns whether modules are transformed in the environment swc runs in (see `Config::envs`).
+fn is_enabled_in_env(envs: Option<&[String]>, env: Option<&str>) -> bool {
+    match envs {
+        None => true,
+        Some(envs) => env.is_some_and(|env| envs.iter().any(|enabled_env| enabled_env == env)),
+    }
+}
+
+/// Extensions of the JavaScript and TypeScript modules,
+/// i.e. of the files that may back a module specifier without an extension.
+pub const MODULE_EXTENSIONS: [&str; 8] = ["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+/// Returns whether the file at `relative_path` is part of the package `import_from` points to,
+/// i.e. it's the runtime mockify is imported from, or another file of its package.
+/// Relative and absolute specifiers are resolved from each module instead (see `imports_itself`).
+///
+/// For example, with `import_from`:
+/// - "promock": "node_modules/promock/main.js", "node_modules/.pnpm/promock@1.0.0/node_modules/promock/main.js"
+/// - "@scope/runtime/mockify": "node_modules/@scope/runtime/index.js"
+fn is_runtime_module(import_from: &str, relative_path: &str) -> bool {
+    if relative_path.is_empty() || import_from.starts_with('.') || import_from.starts_with('/') {
+        return false;
+    }
+
+    // bare specifiers point to a package, any of its files may be imported by the runtime
+    let mut segments = import_from.split('/');
+    let package_name = match (segments.next(), segments.next()) {
+        (Some(scope), Some(name)) if scope.starts_with('@') => format!("{}/{}", scope, name),
+        (Some(name), _) => name.to_string(),
+        _ => return false,
+    };
+    let package_path = format!("node_modules/{}/", package_name);
+    relative_path.starts_with(&package_path)
+        || relative_path.contains(&format!("/{}", package_path))
+}
+
+/// Returns whether the module at `relative_path` is the one a relative or absolute `import_from` resolves to
+/// from that module, i.e. whether it would import itself once mockified.
+///
+/// For example, "src/mockify.ts" with "./mockify", "./mockify.js", or "/project/src/mockify" (for a `base_path` of "/project"),
+/// and "src/mockify/index.ts" with "./index" or "../mockify".
+fn imports_itself(base_path: &str, import_from: &str, relative_path: &str) -> bool {
+    if relative_path.is_empty() {
+        return false;
+    }
+    let specifier = if import_from.starts_with('/') {
+        relative_posix_path(base_path, import_from)
+    } else if import_from.starts_with("./") || import_from.starts_with("../") {
+        resolve_import_specifier(Some(relative_path), import_from)
+    } else {
+        return false;
+    };
+
+    // `./mockify.js` may also point to `mockify.ts`
+    let specifier = strip_module_extension(&specifier);
+    let path = strip_module_extension(relative_path);
+    path == specifier || path == format!("{}/index", specifier.trim_end_matches('/'))
+}
+
+fn strip_module_extension(path: &str) -> &str {
+    match path.rsplit_once('.') {
+        Some((path, extension)) if MODULE_EXTENSIONS.contains(&extension) => path,
+        _ => path,
+    }
+}
+
+/// An export of a module, as seen by the modules importing it (see `Config::verify_exports`)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ExportShape {
+    Value(String),
+    /// An export that only exists at the type level
+    Type(String),
+    Default,
+    /// `export * from "<module>"`
+    ReExportAll(String),
+    /// `export = <expression>`
+    ExportAssignment,
+}
+
+impl fmt::Display for ExportShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportShape::Value(name) => write!(f, "`{name}`"),
+            ExportShape::Type(name) => write!(f, "type `{name}`"),
+            ExportShape::Default => write!(f, "default"),
+            ExportShape::ReExportAll(src) => write!(f, "* from \"{src}\""),
+            ExportShape::ExportAssignment => write!(f, "export ="),
+        }
+    }
+}
+
+/// Returns the exports of a module, regardless of how they are declared,
+/// e.g. `export function a() {}` and `export { _mockified_a as a }` are the same export.
+fn export_shape(items: &[ModuleItem]) -> BTreeSet<ExportShape> {
+    let mut exports = BTreeSet::new();
+    let exported_name = |name: &ModuleExportName| match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(str) => str.value.to_string(),
+    };
+    let named = |name: String, type_only: bool| match name.as_str() {
+        "default" if !type_only => ExportShape::Default,
+        _ if type_only => ExportShape::Type(name),
+        _ => ExportShape::Value(name),
+    };
+
+    for item in items {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            continue;
+        };
+        match decl {
+            ModuleDecl::ExportDecl(export) => {
+                let (names, type_only) = declared_names(&export.decl);
+                for (name, _) in names {
+                    exports.insert(named(name.to_string(), type_only));
+                }
+            }
+            ModuleDecl::ExportNamed(named_export) => {
+                for specifier in &named_export.specifiers {
+                    let (name, type_only) = match specifier {
+                        ExportSpecifier::Named(named_specifier) => (
+                            exported_name(
+                                named_specifier
+                                    .exported
+                                    .as_ref()
+                                    .unwrap_or(&named_specifier.orig),
+                            ),
+                            named_specifier.is_type_only,
+                        ),
+                        ExportSpecifier::Namespace(namespace_specifier) => {
+                            (exported_name(&namespace_specifier.name), false)
+                        }
+                        ExportSpecifier::Default(default_specifier) => {
+                            (default_specifier.exported.sym.to_string(), false)
+                        }
+                    };
+                    exports.insert(named(name, type_only || named_export.type_only));
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::TsInterfaceDecl(..),
+                ..
+            }) => {
+                exports.insert(ExportShape::Type("default".into()));
+            }
+            ModuleDecl::ExportDefaultDecl(..) | ModuleDecl::ExportDefaultExpr(..) => {
+                exports.insert(ExportShape::Default);
+            }
+            ModuleDecl::ExportAll(export_all) => {
+                exports.insert(ExportShape::ReExportAll(export_all.src.value.to_string()));
+            }
+            ModuleDecl::TsExportAssignment(..) => {
+                exports.insert(ExportShape::ExportAssignment);
+            }
+            ModuleDecl::TsImportEquals(import_equals) if import_equals.is_export => {
+                exports.insert(named(
+                    import_equals.id.sym.to_string(),
+                    import_equals.is_type_only,
+                ));
+            }
+            _ => {}
+        }
+    }
+    exports
+}
+
+/// Returns the path of `filename` relative to `base_path`, as a POSIX path, for matching it against the config.
+///
+/// Both paths may be POSIX or Windows paths, with mixed separators, `\\?\` prefixes, UNC paths
+/// and drive letters of any case, e.g.:
+/// - "/foo", "/foo/bar/baz.ts" -> "bar/baz.ts"
+/// - "C:\foo", "c:/foo\bar\baz.ts" -> "bar/baz.ts"
+/// - "\\?\C:\foo", "C:\foo\baz.ts" -> "baz.ts"
+///
+/// Paths outside of the base path start with `../`, unless they're on another root (a drive, a UNC share),
+/// where they stay absolute (e.g. "D:/bar/baz.ts"), so that the config can target both.
+pub fn relative_posix_path(base_path: &str, filename: &str) -> String {
+    if filename.is_empty() || base_path.is_empty() {
+        return "".into();
+    }
+
+    let base_path = NormalizedPath::new(base_path);
+    let filename = NormalizedPath::new(filename);
+    if base_path.root != filename.root {
+        return filename.to_string();
+    }
+
+    let common = base_path
+        .components
+        .iter()
+        .zip(&filename.components)
+        .take_while(|(base, file)| base == file)
+        .count();
+    // the base path cannot go up from the common directory when it's relative, e.g. "../foo" from "bar"
+    if base_path.components[common..]
+        .iter()
+        .any(|component| component == "..")
+    {
+        return filename.to_string();
+    }
+
+    let parents = base_path.components.len() - common;
+    std::iter::repeat_n("..", parents)
+        .chain(filename.components[common..].iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns `path` resolved against the directory `base_dir`, whether they're POSIX or Windows paths,
+/// e.g. the `base_path` of the config against the working directory of swc.
+pub fn resolve_path(base_dir: &str, path: &str) -> String {
+    if NormalizedPath::new(path).root.is_empty() {
+        NormalizedPath::new(&format!("{base_dir}/{path}")).to_string()
+    } else {
+        path.into()
+    }
+}
+
+/// A path split into its root (`/`, `C:/`, `//server/share/`, or empty when it's relative)
+/// and its components, without `.` and with `..` resolved wherever possible.
+struct NormalizedPath {
+    root: String,
+    components: Vec<String>,
+}
+
+impl NormalizedPath {
+    fn new(path: &str) -> Self {
+        let path = path.replace('\\', "/");
+        // the verbatim (`//?/`) and device (`//./`) prefixes of Windows
+        let path = match path
+            .strip_prefix("//?/")
+            .or_else(|| path.strip_prefix("//./"))
+        {
+            Some(rest) => match rest.get(..4) {
+                Some(unc) if unc.eq_ignore_ascii_case("unc/") => format!("//{}", &rest[4..]),
+                _ => rest.to_string(),
+            },
+            None => path,
+        };
+
+        let (root, rest) = if let Some(unc) = path.strip_prefix("//") {
+            // the server and the share name are part of the root, the same as a drive letter
+            let mut parts = unc.splitn(3, '/');
+            let server = parts.next().unwrap_or_default();
+            let share = parts.next().unwrap_or_default();
+            (
+                format!("//{server}/{share}/").to_lowercase(),
+                parts.next().unwrap_or_default(),
+            )
+        } else if path.len() >= 2
+            && path.as_bytes()[1] == b':'
+            && path.as_bytes()[0].is_ascii_alphabetic()
+        {
+            (format!("{}:/", path[..1].to_ascii_uppercase()), &path[2..])
+        } else if let Some(rest) = path.strip_prefix('/') {
+            ("/".to_string(), rest)
+        } else {
+            (String::new(), path.as_str())
+        };
+
+        let mut components: Vec<String> = vec![];
+        for component in rest.split('/') {
+            match component {
+                "" | "." => {}
+                ".." if components.last().is_some_and(|last| last != "..") => {
+                    components.pop();
+                }
+                // the parent of a root is the root itself
+                ".." if !root.is_empty() => {}
+                _ => components.push(component.to_string()),
+            }
+        }
+
+        NormalizedPath { root, components }
+    }
+}
+
+impl fmt::Display for NormalizedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.root, self.components.join("/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use swc_core::{
+        common::{
+            chain,
+            errors::{DiagnosticBuilder, Emitter, Handler},
+            FileName, SourceMap, GLOBALS,
+        },
+        ecma::{
+            ast::EsVersion,
+            parser::{parse_file_as_module, Syntax},
+            transforms::{base::resolver, testing::test_inline},
+        },
+    };
+
+    // Transforming a module twice gives the same result as transforming it once
+    test_inline!(
+        Default::default(),
+        |_| chain!(
+            as_folder(TransformVisitor::new(None)),
+            as_folder(TransformVisitor::new(None))
+        ),
+        transform_twice,
+        // Input codes
+        r#"const A = {};
+        export const b = {};
+        export function c() {}
+        export { A };
+        export default A;"#,
+        // Output codes after transformed with plugin
+        r#""use __mockified__";
+        import { mockify as mockify } from "promock";
+        const _mockified_c = mockify(_actual_c, c, {
+            exportName: "c",
+            kind: "function"
+        });
+        const A = {};
+        export const b = mockify({}, void 0, {
+            exportName: "b",
+            kind: "const"
+        });
+        function _actual_c() {}
+        export { _mockified_A as A };
+        const _mockified_A = mockify(A, void 0, {
+            exportName: "A",
+            kind: "binding"
+        });
+        function c(...args) {
+            return _mockified_c.apply(this, args);
+        }
+        export { _mockified_c as c };
+        export { _mockified_A as default };"#
+    );
+
+    #[test]
+    fn relative_posix_path_of_posix_paths() {
+        assert_eq!(relative_posix_path("/foo", "/foo/bar/baz.ts"), "bar/baz.ts");
+        assert_eq!(
+            relative_posix_path("/foo/", "/foo/./bar//baz.ts"),
+            "bar/baz.ts"
+        );
+        assert_eq!(relative_posix_path("/foo/qux/..", "/foo/baz.ts"), "baz.ts");
+        assert_eq!(relative_posix_path(".", "src/baz.ts"), "src/baz.ts");
+        assert_eq!(relative_posix_path("/foo", "/foo"), "");
+        assert_eq!(relative_posix_path("/foo", ""), "");
+    }
+
+    #[test]
+    fn relative_posix_path_of_windows_paths() {
+        assert_eq!(
+            relative_posix_path(r"C:\foo", r"C:\foo\bar\baz.ts"),
+            "bar/baz.ts"
+        );
+        assert_eq!(
+            relative_posix_path(r"c:\foo", r"C:/foo\bar/baz.ts"),
+            "bar/baz.ts"
+        );
+        assert_eq!(
+            relative_posix_path(r"\\?\C:\foo", r"C:\foo\baz.ts"),
+            "baz.ts"
+        );
+        assert_eq!(
+            relative_posix_path(r"\\server\share\foo", r"\\?\UNC\server\share\foo\baz.ts"),
+            "baz.ts"
+        );
+        assert_eq!(
+            relative_posix_path(r"\\Server\Share", r"\\server\share\baz.ts"),
+            "baz.ts"
+        );
+    }
+
+    #[test]
+    fn relative_posix_path_outside_of_base_path() {
+        assert_eq!(relative_posix_path("/foo/bar", "/foo/baz.ts"), "../baz.ts");
+        assert_eq!(
+            relative_posix_path(r"C:\foo\bar", r"C:\qux\baz.ts"),
+            "../../qux/baz.ts"
+        );
+        assert_eq!(
+            relative_posix_path(r"C:\foo", r"D:\foo\baz.ts"),
+            "D:/foo/baz.ts"
+        );
+        assert_eq!(
+            relative_posix_path(r"C:\foo", r"\\server\share\baz.ts"),
+            "//server/share/baz.ts"
+        );
+        assert_eq!(relative_posix_path(".", "/foo/baz.ts"), "/foo/baz.ts");
+        assert_eq!(relative_posix_path("../foo", "src/baz.ts"), "src/baz.ts");
+    }
+
+    #[test]
+    fn resolve_path_against_base_dir() {
+        assert_eq!(resolve_path("/project", "."), "/project");
+        assert_eq!(
+            resolve_path("/project", "./packages/app"),
+            "/project/packages/app"
+        );
+        assert_eq!(resolve_path("/project/app", "../lib"), "/project/lib");
+        assert_eq!(resolve_path("/project", "/lib"), "/lib");
+        assert_eq!(resolve_path(r"C:\project", "."), "C:/project");
+        assert_eq!(resolve_path(r"C:\project", r"D:\lib"), r"D:\lib");
+    }
+
+    #[test]
+    fn runtime_module_of_bare_specifier() {
+        assert!(is_runtime_module("promock", "node_modules/promock/main.js"));
+        assert!(is_runtime_module(
+            "promock",
+            "node_modules/.pnpm/promock@1.0.0/node_modules/promock/dist/main.js"
+        ));
+        assert!(is_runtime_module(
+            "@scope/runtime/mockify",
+            "../node_modules/@scope/runtime/index.js"
+        ));
+        assert!(!is_runtime_module(
+            "promock",
+            "node_modules/promock-bun/main.js"
+        ));
+        assert!(!is_runtime_module("promock", "src/promock/main.js"));
+        assert!(!is_runtime_module("promock", ""));
+    }
+
+    #[test]
+    fn runtime_module_of_relative_specifier() {
+        assert!(!is_runtime_module("./mockify", "mockify.ts"));
+        assert!(!is_runtime_module("/project/mockify", "mockify.ts"));
+    }
+
+    #[test]
+    fn module_importing_itself() {
+        assert!(imports_itself("/project", "./mockify", "src/mockify.ts"));
+        assert!(imports_itself("/project", "./mockify.js", "src/mockify.ts"));
+        assert!(imports_itself(
+            "/project",
+            "./index",
+            "src/mockify/index.mjs"
+        ));
+        assert!(imports_itself(
+            "/project",
+            "../mockify",
+            "src/mockify/index.ts"
+        ));
+        assert!(imports_itself(
+            "/project",
+            "/project/src/mockify",
+            "src/mockify.ts"
+        ));
+        assert!(!imports_itself(
+            "/project",
+            "./mockify",
+            "src/mockify.test.ts"
+        ));
+        assert!(!imports_itself(
+            "/project",
+            "./test/mockify",
+            "src/mockify.ts"
+        ));
+        assert!(!imports_itself(
+            "/project",
+            "/project/mockify",
+            "src/mockify.ts"
+        ));
+        assert!(!imports_itself(
+            "/project",
+            "promock",
+            "node_modules/promock/main.js"
+        ));
+        assert!(!imports_itself("/project", "./mockify", ""));
+    }
+
+    #[test]
+    fn path_included() {
+        let config = Config {
+            include_paths: Some(vec![Regex::new("^src/").unwrap()]),
+            exclude_paths: Some(vec![Regex::new("\\.test\\.ts$").unwrap()]),
+            ..Default::default()
+        };
+        assert!(config.is_path_included("src/api.ts"));
+        assert!(!config.is_path_included("src/api.test.ts"));
+        assert!(!config.is_path_included("lib/api.ts"));
+        assert!(Config::default().is_path_included("lib/api.ts"));
+        assert!(!Config::default().is_path_included("node_modules/promock/main.js"));
+    }
+
+    #[test]
+    fn enabled_in_env() {
+        let envs = vec!["test".to_string(), "e2e".to_string()];
+        assert!(is_enabled_in_env(None, None));
+        assert!(is_enabled_in_env(None, Some("production")));
+        assert!(is_enabled_in_env(Some(&envs), Some("test")));
+        assert!(is_enabled_in_env(Some(&envs), Some("e2e")));
+        assert!(!is_enabled_in_env(Some(&envs), Some("production")));
+        assert!(!is_enabled_in_env(Some(&envs), Some("testing")));
+        assert!(!is_enabled_in_env(Some(&envs), None));
+        assert!(!is_enabled_in_env(Some(&[]), Some("test")));
+    }
+
+    /// Collects the diagnostics emitted by the transform, as `<level>: <message>`
+    struct DiagnosticCollector(Arc<Mutex<Vec<String>>>);
+
+    impl Emitter for DiagnosticCollector {
+        fn emit(&mut self, diagnostic: &DiagnosticBuilder<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{}: {}", diagnostic.level, diagnostic.message()));
+        }
+    }
+
+    /// Runs `f` with a handler, returning its result, and the diagnostics emitted meanwhile
+    fn collect_diagnostics<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let handler = Handler::with_emitter(
+            true,
+            false,
+            Box::new(DiagnosticCollector(diagnostics.clone())),
+        );
+        let result = GLOBALS.set(&Default::default(), || HANDLER.set(&handler, f));
+        let diagnostics = diagnostics.lock().unwrap().clone();
+        (result, diagnostics)
+    }
+
+    /// Runs the transform on a TypeScript module at `file_path`,
+    /// returning the visitor, and the diagnostics it emitted
+    fn transform_module(
+        config: Config,
+        file_path: &str,
+        input: &str,
+    ) -> (TransformVisitor, Vec<String>) {
+        collect_diagnostics(|| {
+            let cm: Lrc<SourceMap> = Default::default();
+            let fm = cm.new_source_file(Lrc::new(FileName::Anon), input.into());
+            let mut module = parse_file_as_module(
+                &fm,
+                Syntax::Typescript(Default::default()),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+            module.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), true));
+
+            let mut visitor = TransformVisitor::new(Some(config));
+            visitor.file_path = Some(file_path.into());
+            module.visit_mut_with(&mut visitor);
+            visitor
+        })
+    }
+
+    /// Returns what the transform reports with `Config::explain` for a TypeScript module
+    fn explain(config: Config, input: &str) -> Vec<String> {
+        let (mut visitor, _) = transform_module(config, "src/module.ts", input);
+        visitor.reports.sort_by_key(|report| report.span.lo);
+        visitor.reports.iter().map(ExportReport::message).collect()
+    }
+
+    #[test]
+    fn explain_exports() {
+        let reports = explain(
+            Config::default(),
+            r#"
+            import { helper } from "./helper";
+            export const a = 1, { b } = helper;
+            export let c = 1;
+            export declare const d: number;
+            export function e() {}
+            export class F {}
+            export const enum G { A }
+            export interface H {}
+            export type I = string;
+            export namespace J { export const k = 1; export var l = 1; }
+            export namespace Types { export type T = string; }
+            export { helper };
+            export { m } from "./m";
+            export * from "./n";
+            export default 1;"#,
+        );
+        assert_eq!(
+            reports,
+            vec![
+                "promock: `a` is mockified (const)",
+                "promock: `b` is not mockified, as destructured bindings are not mockified, \
+                 declare them with `const` and export them with `export { a }` instead",
+                "promock: `c` is not mockified, as `let` and `var` exports can be reassigned, \
+                 only `const` exports are",
+                "promock: `d` is not mockified, as ambient declarations have no runtime value",
+                "promock: `e` is mockified (function)",
+                "promock: `F` is not mockified, as class declarations are not mockified \
+                 where they're exported, export the class with `export { A }` \
+                 or `export default class` instead",
+                "promock: `G` is not mockified, as const enums are inlined by TypeScript",
+                "promock: `H` is not mockified, as it only exists at the type level",
+                "promock: `I` is not mockified, as it only exists at the type level",
+                "promock: `J` is mockified (namespace)",
+                "promock: `J.k` is mockified (const)",
+                "promock: `J.l` is not mockified, as `let` and `var` exports can be reassigned, \
+                 only `const` exports are",
+                "promock: `Types` is not mockified, as it only exists at the type level",
+                "promock: `helper` is mockified (binding)",
+                "promock: `m` is not mockified, as re-exports are left to the module they come from, \
+                 which is mockified if it's included",
+                "promock: `*` is not mockified, as re-exports are left to the module they come from, \
+                 which is mockified if it's included",
+                "promock: `default` is mockified (expression)",
+            ]
+        );
+    }
+
+    #[test]
+    fn explain_imports_and_rewired_bindings() {
+        let config = Config {
+            rewire: true,
+            mock_imports: Some(vec![Regex::new("^lodash$").unwrap()]),
+            ..Default::default()
+        };
+        let reports = explain(
+            config,
+            r#"
+            import { get } from "lodash";
+            function internal() { return get; }
+            export const value = internal();"#,
+        );
+        assert_eq!(
+            reports,
+            vec![
+                "promock: the import `get` from \"lodash\" is mockified",
+                "promock: `internal` is rewired (function)",
+                "promock: `value` is mockified (const)",
+            ]
+        );
+    }
+
+    #[test]
+    fn path_exclusion() {
+        let config = Config {
+            include_paths: Some(vec![Regex::new("^src/").unwrap()]),
+            exclude_paths: Some(vec![Regex::new("\\.test\\.ts$").unwrap()]),
+            ..Default::default()
+        };
+        assert_eq!(config.path_exclusion("src/api.ts"), None);
+        assert_eq!(
+            config.path_exclusion("src/api.test.ts"),
+            Some("its path matches `excludePaths`")
+        );
+        assert_eq!(
+            config.path_exclusion("lib/api.ts"),
+            Some("its path doesn't match `includePaths`")
+        );
+        assert_eq!(
+            Config::default().path_exclusion("node_modules/promock/main.js"),
+            Some("it's the runtime mockify is imported from")
+        );
+    }
+
+    #[test]
+    fn config_overrides() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "basePath": ".",
+                "importFrom": "promock",
+                "mockImports": ["^lodash$"],
+                "overrides": [
+                    { "paths": ["^packages/app/"], "importFrom": "@app/promock", "rewire": true },
+                    { "paths": ["^packages/app/legacy/"], "rewire": false, "mockImports": [] }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let app = config.for_path("packages/app/index.ts");
+        assert_eq!(app.import_from, "@app/promock");
+        assert!(app.rewire);
+        assert_eq!(app.mock_imports.unwrap().len(), 1);
+
+        let legacy = config.for_path("packages/app/legacy/index.ts");
+        assert_eq!(legacy.import_from, "@app/promock");
+        assert!(!legacy.rewire);
+        assert!(legacy.mock_imports.unwrap().is_empty());
+
+        let other = config.for_path("packages/lib/index.ts");
+        assert_eq!(other.import_from, "promock");
+        assert!(!other.rewire);
+
+        // the runtime is excluded by the `import_from` of its path
+        assert_eq!(
+            config
+                .for_path("packages/app/node_modules/@app/promock/main.js")
+                .path_exclusion("packages/app/node_modules/@app/promock/main.js"),
+            Some("it's the runtime mockify is imported from")
+        );
+    }
+
+    #[test]
+    fn strict() {
+        let config = Config {
+            strict: true,
+            strict_allowlist: Some(vec![Regex::new("#legacy$").unwrap()]),
+            ..Default::default()
+        };
+        let (_, diagnostics) = transform_module(
+            config,
+            "src/module.ts",
+            r#"
+            export const a = 1;
+            export let b = 1;
+            export declare function c(): void;
+            export let legacy = 1;
+            export interface D {}
+            export { e } from "./e";"#,
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                "error: promock: `b` cannot be mockified, as `let` and `var` exports can be reassigned, \
+                 only `const` exports are; add `src/module.ts#b` to `strictAllowlist` if it's intentional",
+                "error: promock: `c` cannot be mockified, as ambient declarations have no runtime value; \
+                 add `src/module.ts#c` to `strictAllowlist` if it's intentional",
+                "error: promock: `e` cannot be mockified, as re-exports are left to the module \
+                 they come from, which is mockified if it's included; \
+                 add `src/module.ts#e` to `strictAllowlist` if it's intentional",
+            ]
+        );
+    }
+
+    #[test]
+    fn strict_with_opt_out_directive() {
+        let config = Config {
+            strict: true,
+            ..Default::default()
+        };
+        let (_, diagnostics) = transform_module(
+            config,
+            "src/module.ts",
+            r#""use __do_not_mockify__";
+            export let a = 1;"#,
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn export_shape_ignores_declaration_style() {
+        let parse = |input: &str| {
+            let cm: Lrc<SourceMap> = Default::default();
+            let fm = cm.new_source_file(Lrc::new(FileName::Anon), input.into());
+            parse_file_as_module(
+                &fm,
+                Syntax::Typescript(Default::default()),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .unwrap()
+        };
+        let before = parse(
+            r#"
+            export function a() {}
+            export default class B {}
+            export type C = string;
+            export * from "./d";"#,
+        );
+        let after = parse(
+            r#"
+            function _actual_a() {}
+            class B {}
+            export { _mockified_a as a, _mockified_B as default };
+            export type C = string;
+            export * from "./d";"#,
+        );
+        assert_eq!(export_shape(&before.body), export_shape(&after.body));
+        assert_eq!(
+            export_shape(&before.body),
+            BTreeSet::from([
+                ExportShape::Value("a".into()),
+                ExportShape::Type("C".into()),
+                ExportShape::Default,
+                ExportShape::ReExportAll("./d".into()),
+            ])
+        );
+
+        let renamed = parse("export { _mockified_a as b };");
+        assert_ne!(export_shape(&before.body), export_shape(&renamed.body));
+    }
+
+    #[test]
+    fn verify_exports() {
+        let config = Config {
+            verify_exports: true,
+            ..Default::default()
+        };
+        let (_, diagnostics) = transform_module(
+            config.clone(),
+            "src/module.ts",
+            r#"
+            export function a() {}
+            export default class B {}
+            export { a as c };"#,
+        );
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+        let visitor = TransformVisitor::new(Some(config));
+        let (_, diagnostics) = collect_diagnostics(|| {
+            visitor.verify_exports(
+                DUMMY_SP,
+                BTreeSet::from([ExportShape::Value("a".into()), ExportShape::Default]),
+                BTreeSet::from([
+                    ExportShape::Value("b".into()),
+                    ExportShape::Default,
+                    ExportShape::Value(MODULE_REGISTRY_EXPORT.into()),
+                ]),
+            )
+        });
+        assert_eq!(
+            diagnostics,
+            vec![
+                "error: promock: the transform changed the exports of this module, \
+                  which is a bug of promock (missing: [`a`], added: [`b`])"
+            ]
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! Runs modules before and after the transform in an embedded JavaScript engine,
+//! checking that they behave the same, and that overriding their mockified exports takes effect,
+//! including for the calls made from within the modules themselves.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use boa_engine::{
+    builtins::promise::PromiseState,
+    js_string,
+    module::{ModuleLoader, Referrer},
+    Context, JsError, JsNativeError, JsResult, JsString, Module, Source,
+};
+use promock_swc::{promock_transform, Config};
+use swc_core::{
+    common::{chain, sync::Lrc, FileName, Mark, SourceMap, GLOBALS},
+    ecma::{
+        ast::EsVersion,
+        codegen::{text_writer::JsWriter, Emitter},
+        parser::{parse_file_as_module, EsSyntax, Syntax},
+        transforms::base::{fixer::fixer, hygiene::hygiene, resolver},
+        visit::FoldWith,
+    },
+};
+
+/// A stub of the runtime: `mockify` returns a proxy forwarding to the override of the value, if any,
+/// or to the value itself.
+/// The overrides are kept in a `Map`, as boa crashes on weak maps keyed by proxies.
+const RUNTIME_STUB: &str = r#"
+const overrides = new Map();
+const current = (proxy, target) => (overrides.has(proxy) ? overrides.get(proxy) : target);
+
+export function mockify(value, original, metadata) {
+  if ((typeof value !== "object" && typeof value !== "function") || value === null) {
+    return value;
+  }
+  const proxy = new Proxy(value, {
+    get: (target, key) => Reflect.get(current(proxy, target), key),
+    has: (target, key) => Reflect.has(current(proxy, target), key),
+    apply: (target, thisArg, args) => Reflect.apply(current(proxy, target), thisArg, args),
+    construct: (target, args) => Reflect.construct(current(proxy, target), args),
+  });
+  return proxy;
+}
+
+export function override(proxy, implementation) {
+  overrides.set(proxy, implementation);
+}
+
+export function restore(proxy) {
+  overrides.delete(proxy);
+}
+"#;
+
+/// Path of the module under test, imported by the test module as `./module.js`
+const MODULE_PATH: &str = "/project/src/module.js";
+
+/// Serves the modules from memory, parsing them on their first import.
+struct MemoryModuleLoader {
+    sources: HashMap<String, String>,
+    modules: RefCell<HashMap<String, Module>>,
+}
+
+impl ModuleLoader for MemoryModuleLoader {
+    fn load_imported_module(
+        &self,
+        _referrer: Referrer,
+        specifier: JsString,
+        finish_load: Box<dyn FnOnce(JsResult<Module>, &mut Context)>,
+        context: &mut Context,
+    ) {
+        let specifier = specifier.to_std_string_escaped();
+        let result = match self.modules.borrow().get(&specifier) {
+            Some(module) => Ok(module.clone()),
+            None => match self.sources.get(&specifier) {
+                Some(source) => Module::parse(Source::from_bytes(source), None, context),
+                None => Err(JsNativeError::typ()
+                    .with_message(format!("unknown module {specifier}"))
+                    .into()),
+            },
+        };
+        if let Ok(module) = &result {
+            self.modules.borrow_mut().insert(specifier, module.clone());
+        }
+        finish_load(result, context);
+    }
+}
+
+/// Returns the module transformed with the config, printed as JavaScript.
+fn transform(config: Config, source: &str) -> String {
+    GLOBALS.set(&Default::default(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(Lrc::new(FileName::Real(MODULE_PATH.into())), source.into());
+        let module = parse_file_as_module(
+            &fm,
+            Syntax::Es(EsSyntax::default()),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        )
+        .expect("failed to parse the module");
+
+        let module = module.fold_with(&mut chain!(
+            resolver(Mark::new(), Mark::new(), false),
+            promock_transform(config, MODULE_PATH),
+            hygiene(),
+            fixer(None)
+        ));
+
+        let mut code = vec![];
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm, "\n", &mut code, None),
+        };
+        emitter.emit_module(&module).unwrap();
+        String::from_utf8(code).unwrap()
+    })
+}
+
+/// Runs the test module, importing the module under test from `./module.js`,
+/// and returns its `result` export, serialized as JSON.
+fn run(module: &str, test: &str) -> Result<String, String> {
+    let loader = Rc::new(MemoryModuleLoader {
+        sources: HashMap::from([
+            ("promock".to_string(), RUNTIME_STUB.to_string()),
+            ("./module.js".to_string(), module.to_string()),
+        ]),
+        modules: Default::default(),
+    });
+    let context = &mut Context::builder().module_loader(loader).build().unwrap();
+    let display_error = |error: JsError| error.to_string();
+
+    let test = Module::parse(Source::from_bytes(test), None, context).map_err(display_error)?;
+    let promise = test.load_link_evaluate(context);
+    context.run_jobs();
+    if let PromiseState::Rejected(error) = promise.state() {
+        return Err(JsError::from_opaque(error).to_string());
+    }
+
+    let result = test
+        .namespace(context)
+        .get(js_string!("result"), context)
+        .map_err(display_error)?;
+    let json = context
+        .global_object()
+        .get(js_string!("JSON"), context)
+        .and_then(|json| {
+            let stringify = json
+                .as_object()
+                .unwrap()
+                .get(js_string!("stringify"), context)?;
+            stringify
+                .as_callable()
+                .unwrap()
+                .call(&json, &[result], context)
+        })
+        .map_err(display_error)?;
+    Ok(json
+        .to_string(context)
+        .map_err(display_error)?
+        .to_std_string_escaped())
+}
+
+fn config() -> Config {
+    Config {
+        base_path: "/project".into(),
+        verify_exports: true,
+        ..Default::default()
+    }
+}
+
+/// Asserts that the test module gets the same result from the module before and after the transform.
+fn assert_same_behavior(config: Config, module: &str, test: &str) {
+    let transformed = transform(config, module);
+    let expected = run(module, test).expect("failed to run the original module");
+    let actual = run(&transformed, test).unwrap_or_else(|error| {
+        panic!("failed to run the transformed module: {error}\n{transformed}")
+    });
+    assert_eq!(expected, actual, "transformed module:\n{transformed}");
+}
+
+/// Asserts the result of the test module, running against the transformed module.
+fn assert_transformed_result(config: Config, module: &str, test: &str, expected: &str) {
+    let transformed = transform(config, module);
+    let actual = run(&transformed, test).unwrap_or_else(|error| {
+        panic!("failed to run the transformed module: {error}\n{transformed}")
+    });
+    assert_eq!(expected, actual, "transformed module:\n{transformed}");
+}
+
+const MODULE: &str = r#"
+export const config = { retries: 3, name: "api" };
+export function add(a, b) {
+  return a + b;
+}
+export function sum(...values) {
+  return values.reduce((total, value) => add(total, value), 0);
+}
+function double(value) {
+  return add(value, value);
+}
+export { double };
+export class Counter {
+  count = 0;
+  static create() {
+    return new Counter();
+  }
+  increment() {
+    this.count = add(this.count, 1);
+    return this;
+  }
+}
+export default function greet(name) {
+  return `hello ${name} (${config.name})`;
+}
+"#;
+
+#[test]
+fn same_behavior() {
+    assert_same_behavior(
+        config(),
+        MODULE,
+        r#"
+        import greet, { config, add, sum, double, Counter } from "./module.js";
+        const counter = Counter.create().increment().increment();
+        export const result = {
+          config,
+          add: add(1, 2),
+          addLength: add.length,
+          sum: sum(1, 2, 3),
+          double: double(4),
+          count: counter.count,
+          isCounter: counter instanceof Counter,
+          greet: greet("world"),
+        };
+        "#,
+    );
+}
+
+#[test]
+fn same_behavior_with_rewire() {
+    assert_same_behavior(
+        Config {
+            rewire: true,
+            ..config()
+        },
+        r#"
+        const base = 10;
+        function offset(value) {
+          return value + base;
+        }
+        class Point {
+          constructor(x) {
+            this.x = offset(x);
+          }
+        }
+        export function point(x) {
+          return new Point(x).x;
+        }
+        "#,
+        r#"
+        import { point } from "./module.js";
+        export const result = point(1);
+        "#,
+    );
+}
+
+#[test]
+fn calls_during_evaluation() {
+    assert_same_behavior(
+        config(),
+        r#"
+        export const three = add(1, 2);
+        export function add(a, b) {
+          return a + b;
+        }
+        "#,
+        r#"
+        import { three } from "./module.js";
+        export const result = three;
+        "#,
+    );
+}
+
+#[test]
+fn calls_during_evaluation_with_rewire() {
+    assert_same_behavior(
+        Config {
+            rewire: true,
+            ..config()
+        },
+        r#"
+        export const three = add(1, 2);
+        function add(a, b) {
+          return a + b;
+        }
+        "#,
+        r#"
+        import { three } from "./module.js";
+        export const result = three;
+        "#,
+    );
+}
+
+#[test]
+fn calls_during_evaluation_of_default_and_named_exports() {
+    assert_same_behavior(
+        config(),
+        r#"
+        export const results = [double(1), sum(1, 2)];
+        export default function double(a) {
+          return a * 2;
+        }
+        function sum(a, b) {
+          return a + b;
+        }
+        export { sum };
+        "#,
+        r#"
+        import double, { results, sum } from "./module.js";
+        export const result = [...results, double(2), sum(2, 3)];
+        "#,
+    );
+}
+
+#[test]
+fn default_export_of_reassigned_binding() {
+    assert_same_behavior(
+        config(),
+        r#"
+        let count = { value: 1 };
+        export default count;
+        count = { value: 2 };
+        export function current() {
+          return count.value;
+        }
+        "#,
+        r#"
+        import count, { current } from "./module.js";
+        export const result = { exported: count.value, current: current() };
+        "#,
+    );
+}
+
+#[test]
+fn overrides_take_effect() {
+    assert_transformed_result(
+        config(),
+        MODULE,
+        r#"
+        import greet, { config, add, sum, double, Counter } from "./module.js";
+        import { override, restore } from "promock";
+        override(add, (a, b) => a * b);
+        override(config, { name: "mock" });
+        const overridden = {
+          add: add(2, 3),
+          sum: sum(1, 2, 3),
+          double: double(3),
+          count: Counter.create().increment().count,
+          greet: greet("world"),
+        };
+        restore(add);
+        restore(config);
+        export const result = { overridden, restored: sum(1, 2, 3) };
+        "#,
+        r#"{"overridden":{"add":6,"sum":0,"double":9,"count":0,"greet":"hello world (mock)"},"restored":6}"#,
+    );
+}
+
+#[test]
+fn overrides_of_rewired_bindings_take_effect() {
+    assert_transformed_result(
+        Config {
+            rewire: true,
+            ..config()
+        },
+        r#"
+        function offset(value) {
+          return value + 10;
+        }
+        export function point(x) {
+          return offset(x);
+        }
+        "#,
+        r#"
+        import { point, __promock_rewire__ } from "./module.js";
+        import { override } from "promock";
+        override(__promock_rewire__.offset, (value) => value - 10);
+        export const result = point(1);
+        "#,
+        "-9",
+    );
+}
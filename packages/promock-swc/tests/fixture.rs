@@ -0,0 +1,147 @@
+//! Runs the transform on the fixtures of `tests/fixture/<name>/`: the module in `input.js` or `input.ts`,
+//! with the config of the plugin in the optional `config.json`, comparing the transformed module to `output.js`,
+//! and the diagnostics of the transform (e.g. the errors of `strict`, the warnings of `explain`) to `output.stderr`.
+//!
+//! Run with `UPDATE=1` to write the outputs instead (`pnpm test:update-snapshot` in `swc-plugin-promock`).
+
+use std::{
+    cell::RefCell,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use promock_swc::{promock_transform_with_source_map, Config};
+use serde_json::{json, Value};
+use swc_core::{
+    common::{
+        chain,
+        errors::{DiagnosticBuilder, Emitter, Handler, HandlerFlags, Level, HANDLER},
+        sync::Lrc,
+        BytePos, Mark, SourceMap, SourceMapper,
+    },
+    ecma::{
+        ast::Program,
+        parser::{EsSyntax, Syntax, TsSyntax},
+        transforms::{
+            base::resolver,
+            testing::{test_fixture, FixtureTestConfig},
+        },
+        visit::Fold,
+    },
+};
+use testing::NormalizedOutput;
+
+/// Reads the config of the fixture, with the paths of the fixture relative to its directory,
+/// and the exports verified, so that a fixture also fails when the transform changes the exports of its module.
+fn read_config(dir: &Path) -> Config {
+    let mut config = match fs::read_to_string(dir.join("config.json")) {
+        Ok(json) => serde_json::from_str(&json).expect("failed to parse config.json"),
+        Err(_) => json!({}),
+    };
+    config["basePath"] = dir.to_string_lossy().into();
+    if config.get("verifyExports").is_none() {
+        config["verifyExports"] = Value::Bool(true);
+    }
+    serde_json::from_value(config).expect("invalid config.json")
+}
+
+#[testing::fixture("tests/fixture/**/input.*")]
+fn fixture(input: PathBuf) {
+    let dir = input.parent().unwrap();
+    let config = read_config(dir);
+    let syntax = match input.extension().and_then(|extension| extension.to_str()) {
+        Some("ts") => Syntax::Typescript(TsSyntax::default()),
+        _ => Syntax::Es(EsSyntax {
+            import_attributes: true,
+            ..Default::default()
+        }),
+    };
+
+    let diagnostics = Rc::new(RefCell::new(String::new()));
+    test_fixture(
+        syntax,
+        &|t| {
+            let source_map: Lrc<dyn SourceMapper> = t.cm.clone();
+            RecordDiagnostics {
+                pass: chain!(
+                    resolver(Mark::new(), Mark::new(), syntax.typescript()),
+                    promock_transform_with_source_map(
+                        config.clone(),
+                        &input.to_string_lossy(),
+                        source_map
+                    )
+                ),
+                cm: t.cm.clone(),
+                output: diagnostics.clone(),
+            }
+        },
+        &input,
+        &dir.join("output.js"),
+        FixtureTestConfig {
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+    NormalizedOutput::from(diagnostics.take())
+        .compare_to_file(dir.join("output.stderr"))
+        .unwrap();
+}
+
+/// Runs the pass with a handler of its own, writing its diagnostics to `output`, one per line, with their position,
+/// as the handler of the fixture testing of swc drops the warnings.
+struct RecordDiagnostics<P> {
+    pass: P,
+    cm: Lrc<SourceMap>,
+    output: Rc<RefCell<String>>,
+}
+
+impl<P: Fold> Fold for RecordDiagnostics<P> {
+    fn fold_program(&mut self, program: Program) -> Program {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let handler = Handler::with_emitter_and_flags(
+            Box::new(DiagnosticCollector(diagnostics.clone())),
+            HandlerFlags {
+                can_emit_warnings: true,
+                ..Default::default()
+            },
+        );
+        let program = HANDLER.set(&handler, || self.pass.fold_program(program));
+
+        let mut output = self.output.borrow_mut();
+        for (level, message, position) in diagnostics.lock().unwrap().drain(..) {
+            write!(output, "{level}: {message}").unwrap();
+            if let Some(position) = position {
+                let loc = self.cm.lookup_char_pos(position);
+                write!(
+                    output,
+                    " ({}:{}:{})",
+                    loc.file.name,
+                    loc.line,
+                    loc.col_display + 1
+                )
+                .unwrap();
+            }
+            output.push('\n');
+        }
+        program
+    }
+}
+
+/// The level, the message and the position of a diagnostic
+type Diagnostic = (Level, String, Option<BytePos>);
+
+/// Collects the diagnostics
+struct DiagnosticCollector(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl Emitter for DiagnosticCollector {
+    fn emit(&mut self, diagnostic: &DiagnosticBuilder<'_>) {
+        let position = diagnostic.span.primary_span().map(|span| span.lo);
+        self.0
+            .lock()
+            .unwrap()
+            .push((diagnostic.level, diagnostic.message(), position));
+    }
+}
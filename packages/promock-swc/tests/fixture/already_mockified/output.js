@@ -0,0 +1,6 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export const example = mockify({});
+const A = {};
+export { _mockified_A as A };
+const _mockified_A = mockify(A);
@@ -0,0 +1 @@
+export async function asyncFunc() { return Promise.resolve(); }
@@ -0,0 +1,16 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_asyncFunc = mockify(_actual_asyncFunc, asyncFunc, {
+    path: "input.js",
+    exportName: "asyncFunc",
+    kind: "function",
+    line: 1,
+    column: 1
+});
+async function _actual_asyncFunc() {
+    return Promise.resolve();
+}
+function asyncFunc(...args) {
+    return _mockified_asyncFunc.apply(this, args);
+}
+export { _mockified_asyncFunc as asyncFunc };
@@ -0,0 +1,16 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_stream = mockify(_actual_stream, stream, {
+    path: "input.js",
+    exportName: "stream",
+    kind: "function",
+    line: 1,
+    column: 1
+});
+async function* _actual_stream() {
+    yield {};
+}
+function stream(...args) {
+    return _mockified_stream.apply(this, args);
+}
+export { _mockified_stream as stream };
@@ -0,0 +1,5 @@
+interface A {}
+{
+    const A = 1;
+}
+export { A };
@@ -0,0 +1,11 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export const nested = mockify({
+    example: {}
+}, void 0, {
+    path: "input.js",
+    exportName: "nested",
+    kind: "const",
+    line: 1,
+    column: 14
+});
@@ -0,0 +1,9 @@
+"use __mockified__";
+import { customMockify as ___customMockify } from "custom-mockify";
+export const example = ___customMockify({}, void 0, {
+    path: "input.js",
+    exportName: "example",
+    kind: "const",
+    line: 1,
+    column: 14
+});
@@ -0,0 +1,4 @@
+function A() {}
+export { A };
+export default A;
+export { A as B, A as C };
@@ -0,0 +1,13 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_A = mockify(A, void 0, {
+    path: "input.js",
+    exportName: "A",
+    kind: "binding",
+    line: 2,
+    column: 10
+});
+function A() {}
+export { _mockified_A as A };
+export { _mockified_A as default };
+export { _mockified_A as B, _mockified_A as C };
@@ -0,0 +1,9 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export default mockify({}, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "expression",
+    line: 1,
+    column: 1
+});
@@ -0,0 +1,11 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export default mockify(class {
+    run() {}
+}, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "class",
+    line: 1,
+    column: 1
+});
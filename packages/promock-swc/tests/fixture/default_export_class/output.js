@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+class Example {
+}
+export { _mockified_Example as default };
+const _mockified_Example = mockify(Example, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "class",
+    line: 1,
+    column: 1
+});
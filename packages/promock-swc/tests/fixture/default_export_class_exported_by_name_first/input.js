@@ -0,0 +1,2 @@
+export { C as D };
+export default class C {}
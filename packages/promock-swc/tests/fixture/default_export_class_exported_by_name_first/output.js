@@ -0,0 +1,27 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+class C {
+}
+export { _mockified_C as D };
+export { _mockified_C as default };
+const _mockified_C = mockify(C, void 0, {
+    path: "input.js",
+    exportName: "D",
+    kind: "binding",
+    line: 1,
+    column: 10
+});
+const _promock_module = {
+    path: "input.js",
+    exports: [
+        {
+            name: "D",
+            kind: "binding"
+        },
+        {
+            name: "default",
+            kind: "class"
+        }
+    ]
+};
+export { _promock_module as __promock_module__ };
@@ -0,0 +1,2 @@
+export default class Example {}
+export { Example as Named };
@@ -0,0 +1,13 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+function example() {
+    return {};
+}
+const _mockified_example = mockify(example, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "function",
+    line: 1,
+    column: 1
+});
+export { _mockified_example as default };
@@ -0,0 +1,2 @@
+export { f as g };
+export default function f() {}
@@ -0,0 +1,26 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+function f() {}
+const _mockified_f = mockify(f, void 0, {
+    path: "input.js",
+    exportName: "g",
+    kind: "binding",
+    line: 1,
+    column: 10
+});
+export { _mockified_f as g };
+export { _mockified_f as default };
+const _promock_module = {
+    path: "input.js",
+    exports: [
+        {
+            name: "g",
+            kind: "binding"
+        },
+        {
+            name: "default",
+            kind: "function"
+        }
+    ]
+};
+export { _promock_module as __promock_module__ };
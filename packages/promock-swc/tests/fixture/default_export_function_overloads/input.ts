@@ -0,0 +1,3 @@
+export default function example(a: string): string;
+export default function example(a: number): number;
+export default function example(a: any) { return a; }
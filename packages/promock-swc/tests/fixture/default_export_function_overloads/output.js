@@ -0,0 +1,15 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+function example(a: string): string;
+function example(a: number): number;
+function example(a: any) {
+    return a;
+}
+const _mockified_example = mockify(example, void 0, {
+    path: "input.ts",
+    exportName: "default",
+    kind: "function",
+    line: 3,
+    column: 1
+});
+export { _mockified_example as default };
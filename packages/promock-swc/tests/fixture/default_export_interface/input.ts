@@ -0,0 +1,4 @@
+export default interface Options {
+  verbose: boolean;
+}
+export const defaults: Options = { verbose: false };
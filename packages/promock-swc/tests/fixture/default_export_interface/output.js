@@ -0,0 +1,14 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export default interface Options {
+    verbose: boolean;
+}
+export const defaults: Options = mockify({
+    verbose: false
+}, void 0, {
+    path: "input.ts",
+    exportName: "defaults",
+    kind: "const",
+    line: 4,
+    column: 14
+});
@@ -0,0 +1,3 @@
+let count = 1;
+export default count;
+count = 2;
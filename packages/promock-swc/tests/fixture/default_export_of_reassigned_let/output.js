@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+let count = 1;
+const _mockified_count = mockify(count, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "binding",
+    line: 2,
+    column: 1
+});
+count = 2;
+export { _mockified_count as default };
@@ -0,0 +1,2 @@
+"use __do_not_mockify__";
+export const example = {};
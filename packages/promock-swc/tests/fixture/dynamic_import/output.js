@@ -0,0 +1 @@
+const module = import('./module');
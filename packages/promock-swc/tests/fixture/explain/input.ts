@@ -0,0 +1,8 @@
+import { external } from "./external.js";
+export const value = {};
+export let mutable = {};
+export function fn() {}
+export type Type = string;
+export { external };
+export { reExported } from "./other.js";
+export * from "./all.js";
@@ -0,0 +1,34 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import { external } from "./external.js";
+const _mockified_fn = mockify(_actual_fn, fn, {
+    path: "input.ts",
+    exportName: "fn",
+    kind: "function",
+    line: 4,
+    column: 1
+});
+export const value = mockify({}, void 0, {
+    path: "input.ts",
+    exportName: "value",
+    kind: "const",
+    line: 2,
+    column: 14
+});
+export let mutable = {};
+function _actual_fn() {}
+export type Type = string;
+export { _mockified_external as external };
+export { reExported } from "./other.js";
+export * from "./all.js";
+function fn(...args) {
+    return _mockified_fn.apply(this, args);
+}
+export { _mockified_fn as fn };
+const _mockified_external = mockify(external, void 0, {
+    path: "input.ts",
+    exportName: "external",
+    kind: "binding",
+    line: 6,
+    column: 10
+});
@@ -0,0 +1,11 @@
+export abstract class Shape {
+  abstract area(): number;
+}
+export class Square extends Shape {
+  constructor(private side: number) {
+    super();
+  }
+  area() {
+    return this.side ** 2;
+  }
+}
@@ -0,0 +1,3 @@
+export * from "./helpers.js";
+export * as utils from "./utils.js";
+export const local = {};
@@ -0,0 +1,11 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export * from "./helpers.js";
+export * as utils from "./utils.js";
+export const local = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "local",
+    kind: "const",
+    line: 3,
+    column: 14
+});
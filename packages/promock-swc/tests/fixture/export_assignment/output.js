@@ -0,0 +1,10 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const api = {};
+export = mockify(api, void 0, {
+    path: "input.ts",
+    exportName: "export=",
+    kind: "exportAssignment",
+    line: 2,
+    column: 1
+})
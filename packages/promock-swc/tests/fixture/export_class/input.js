@@ -0,0 +1,6 @@
+export class Client {
+  static create() {
+    return new Client();
+  }
+}
+export class Server extends Client {}
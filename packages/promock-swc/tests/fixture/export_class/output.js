@@ -0,0 +1,7 @@
+export class Client {
+    static create() {
+        return new Client();
+    }
+}
+export class Server extends Client {
+}
@@ -0,0 +1,9 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export const example = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "example",
+    kind: "const",
+    line: 1,
+    column: 14
+});
@@ -0,0 +1,3 @@
+export const enum A { One }
+const enum B { Two }
+export { B };
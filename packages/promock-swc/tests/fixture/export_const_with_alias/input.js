@@ -0,0 +1,3 @@
+export { A as B };
+export const A = {};
+export { A as C };
@@ -0,0 +1,11 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export { A as B };
+export const A = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "A",
+    kind: "const",
+    line: 2,
+    column: 14
+});
+export { A as C };
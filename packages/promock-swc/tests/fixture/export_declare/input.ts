@@ -0,0 +1,5 @@
+export declare const A: {};
+export declare function B(): void;
+export declare class C {}
+declare const D: {};
+export { D };
@@ -0,0 +1,5 @@
+const source = { a: {}, b: [{}] };
+export const {
+  a,
+  b: [first],
+} = source;
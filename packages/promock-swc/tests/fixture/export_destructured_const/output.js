@@ -0,0 +1,7 @@
+const source = {
+    a: {},
+    b: [
+        {}
+    ]
+};
+export const { a, b: [first] } = source;
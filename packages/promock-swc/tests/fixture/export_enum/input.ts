@@ -0,0 +1 @@
+export enum Status { Active, Inactive }
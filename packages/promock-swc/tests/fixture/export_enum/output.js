@@ -0,0 +1,14 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+enum Status {
+    Active,
+    Inactive
+}
+const _mockified_Status = mockify(Status, void 0, {
+    path: "input.ts",
+    exportName: "Status",
+    kind: "enum",
+    line: 1,
+    column: 1
+});
+export { _mockified_Status as Status };
@@ -0,0 +1,4 @@
+export { E as F };
+export enum E {
+  A,
+}
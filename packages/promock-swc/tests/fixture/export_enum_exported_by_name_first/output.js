@@ -0,0 +1,28 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export { _mockified_E as F };
+enum E {
+    A
+}
+const _mockified_E = mockify(E, void 0, {
+    path: "input.ts",
+    exportName: "F",
+    kind: "binding",
+    line: 1,
+    column: 10
+});
+export { _mockified_E as E };
+const _promock_module = {
+    path: "input.ts",
+    exports: [
+        {
+            name: "F",
+            kind: "binding"
+        },
+        {
+            name: "E",
+            kind: "enum"
+        }
+    ]
+};
+export { _promock_module as __promock_module__ };
@@ -0,0 +1 @@
+export function example() { return {}; }
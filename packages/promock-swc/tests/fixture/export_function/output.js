@@ -0,0 +1,16 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_example = mockify(_actual_example, example, {
+    path: "input.js",
+    exportName: "example",
+    kind: "function",
+    line: 1,
+    column: 1
+});
+function _actual_example() {
+    return {};
+}
+function example(...args) {
+    return _mockified_example.apply(this, args);
+}
+export { _mockified_example as example };
@@ -0,0 +1,2 @@
+const _actual_args = 1;
+export function args() { return _actual_args; }
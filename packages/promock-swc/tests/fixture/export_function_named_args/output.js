@@ -0,0 +1,17 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_args = mockify(_actual_args1, args, {
+    path: "input.js",
+    exportName: "args",
+    kind: "function",
+    line: 2,
+    column: 1
+});
+const _actual_args = 1;
+function _actual_args1() {
+    return _actual_args;
+}
+function args(...args1) {
+    return _mockified_args.apply(this, args1);
+}
+export { _mockified_args as args };
@@ -0,0 +1,15 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_f = mockify(_actual_f, f, {
+    path: "input.js",
+    exportName: "f",
+    kind: "function",
+    line: 2,
+    column: 1
+});
+export { _mockified_f as g };
+function _actual_f() {}
+function f(...args) {
+    return _mockified_f.apply(this, args);
+}
+export { _mockified_f as f };
@@ -0,0 +1,3 @@
+import fs = require("fs");
+export import A = fs.promises;
+export import type B = require("./b");
@@ -0,0 +1,2 @@
+import { A } from 'module';
+export { A as ABC };
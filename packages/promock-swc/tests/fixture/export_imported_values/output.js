@@ -0,0 +1,11 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import { A } from 'module';
+export { _mockified_A as ABC };
+const _mockified_A = mockify(A, void 0, {
+    path: "input.js",
+    exportName: "ABC",
+    kind: "binding",
+    line: 2,
+    column: 10
+});
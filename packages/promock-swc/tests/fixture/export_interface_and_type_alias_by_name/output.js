@@ -0,0 +1,4 @@
+interface A {}
+type B = string;
+export { A, B };
+export default A;
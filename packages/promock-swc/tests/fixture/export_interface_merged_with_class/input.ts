@@ -0,0 +1,3 @@
+interface A { a: string }
+class A {}
+export { A };
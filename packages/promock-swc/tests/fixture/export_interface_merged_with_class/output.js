@@ -0,0 +1,15 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+interface A {
+    a: string;
+}
+class A {
+}
+export { _mockified_A as A };
+const _mockified_A = mockify(A, void 0, {
+    path: "input.ts",
+    exportName: "A",
+    kind: "binding",
+    line: 3,
+    column: 10
+});
@@ -0,0 +1,4 @@
+export let counter = {};
+export var legacy = {};
+export let uninitialized;
+export const frozen = {};
@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export let counter = {};
+export var legacy = {};
+export let uninitialized;
+export const frozen = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "frozen",
+    kind: "const",
+    line: 4,
+    column: 14
+});
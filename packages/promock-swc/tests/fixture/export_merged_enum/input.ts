@@ -0,0 +1,2 @@
+export enum Status { Active = 1 }
+export enum Status { Inactive = 2 }
@@ -0,0 +1,4 @@
+const a = {};
+export const example = {};
+  export function exampleFn() {}
+export { a as b };
@@ -0,0 +1,30 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_exampleFn = mockify(_actual_exampleFn, exampleFn, {
+    path: "input.js",
+    exportName: "exampleFn",
+    kind: "function",
+    line: 3,
+    column: 3
+});
+const a = {};
+export const example = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "example",
+    kind: "const",
+    line: 2,
+    column: 14
+});
+function _actual_exampleFn() {}
+export { _mockified_a as b };
+function exampleFn(...args) {
+    return _mockified_exampleFn.apply(this, args);
+}
+export { _mockified_exampleFn as exampleFn };
+const _mockified_a = mockify(a, void 0, {
+    path: "input.js",
+    exportName: "b",
+    kind: "binding",
+    line: 4,
+    column: 10
+});
@@ -0,0 +1,9 @@
+export namespace Utils {
+    export const config = {};
+    export function f(a: string): string;
+    export function f(a: any) { return g(a); }
+    function g(a: any) { return a; }
+    export namespace Nested {
+        export function h() {}
+    }
+}
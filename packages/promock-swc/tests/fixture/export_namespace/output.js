@@ -0,0 +1,43 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+namespace Utils {
+    export const f = mockify(_actual_f, void 0, {
+        path: "input.ts",
+        exportName: "Utils.f",
+        kind: "function",
+        line: 4,
+        column: 5
+    });
+    export const config = mockify({}, void 0, {
+        path: "input.ts",
+        exportName: "Utils.config",
+        kind: "const",
+        line: 2,
+        column: 18
+    });
+    function _actual_f(a: string): string;
+    function _actual_f(a: any) {
+        return g(a);
+    }
+    function g(a: any) {
+        return a;
+    }
+    export namespace Nested {
+        export const h = mockify(_actual_h, void 0, {
+            path: "input.ts",
+            exportName: "Utils.Nested.h",
+            kind: "function",
+            line: 7,
+            column: 9
+        });
+        function _actual_h() {}
+    }
+}
+const _mockified_Utils = mockify(Utils, void 0, {
+    path: "input.ts",
+    exportName: "Utils",
+    kind: "namespace",
+    line: 1,
+    column: 1
+});
+export { _mockified_Utils as Utils };
@@ -0,0 +1,3 @@
+import type { A } from "./a";
+import { type B, C } from "./b";
+export { A, B, C };
@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import type { A } from "./a";
+import { type B, C } from "./b";
+export { A, B, _mockified_C as C };
+const _mockified_C = mockify(C, void 0, {
+    path: "input.ts",
+    exportName: "C",
+    kind: "binding",
+    line: 3,
+    column: 16
+});
@@ -0,0 +1,8 @@
+export namespace Types {
+    export type A = string;
+    export interface B {}
+}
+namespace Other {
+    export type C = string;
+}
+export { Other };
@@ -0,0 +1,3 @@
+export function example(a: string): string;
+export function example(a: number): number;
+export function example(a: any) { return a; }
@@ -0,0 +1,20 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_example = mockify(_actual_example, example, {
+    path: "input.ts",
+    exportName: "example",
+    kind: "function",
+    line: 3,
+    column: 1
+});
+function _actual_example(a: string): string;
+function _actual_example(a: number): number;
+function _actual_example(a: any) {
+    return a;
+}
+function example(a: string): string;
+function example(a: number): number;
+function example(...args) {
+    return _mockified_example.apply(this, args);
+}
+export { _mockified_example as example };
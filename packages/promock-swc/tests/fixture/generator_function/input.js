@@ -0,0 +1 @@
+export function* genFunc() { yield 1; }
@@ -0,0 +1,16 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_genFunc = mockify(_actual_genFunc, genFunc, {
+    path: "input.js",
+    exportName: "genFunc",
+    kind: "function",
+    line: 1,
+    column: 1
+});
+function* _actual_genFunc() {
+    yield 1;
+}
+function genFunc(...args) {
+    return _mockified_genFunc.apply(this, args);
+}
+export { _mockified_genFunc as genFunc };
@@ -0,0 +1,2 @@
+export const example = {};
+export function fn() {}
@@ -0,0 +1,24 @@
+"use __mockified__";
+function mockify(value, internalWrapper, metadata) {
+    const runtime = globalThis.__promock__;
+    return runtime ? runtime.mockify(value, internalWrapper, metadata) : value;
+}
+const _mockified_fn = mockify(_actual_fn, fn, {
+    path: "input.js",
+    exportName: "fn",
+    kind: "function",
+    line: 2,
+    column: 1
+});
+export const example = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "example",
+    kind: "const",
+    line: 1,
+    column: 14
+});
+function _actual_fn() {}
+function fn(...args) {
+    return _mockified_fn.apply(this, args);
+}
+export { _mockified_fn as fn };
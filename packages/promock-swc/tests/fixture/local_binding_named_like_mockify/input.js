@@ -0,0 +1,2 @@
+const mockify = (value) => value;
+export const example = mockify({});
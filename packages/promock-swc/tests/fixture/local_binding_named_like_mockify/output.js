@@ -0,0 +1,10 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const mockify1 = (value)=>value;
+export const example = mockify(mockify1({}), void 0, {
+    path: "input.js",
+    exportName: "example",
+    kind: "const",
+    line: 2,
+    column: 14
+});
@@ -0,0 +1,16 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export default mockify(function() {}, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "function",
+    line: 1,
+    column: 1
+});
+export const name = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "name",
+    kind: "const",
+    line: 2,
+    column: 14
+});
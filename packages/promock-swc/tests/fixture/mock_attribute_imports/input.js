@@ -0,0 +1,4 @@
+import fixtures from "./fixtures.json" with { type: "json" };
+import config from "./config.json";
+import { helper } from "./helper.js";
+export const data = [fixtures, config, helper];
@@ -0,0 +1,34 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import _imported_fixtures from "./fixtures.json" with {
+    type: "json"
+};
+import _imported_config from "./config.json";
+import { helper } from "./helper.js";
+const fixtures = mockify(_imported_fixtures, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "import",
+    importedFrom: "fixtures.json",
+    line: 1,
+    column: 8
+});
+const config = mockify(_imported_config, void 0, {
+    path: "input.js",
+    exportName: "default",
+    kind: "import",
+    importedFrom: "config.json",
+    line: 2,
+    column: 8
+});
+export const data = mockify([
+    fixtures,
+    config,
+    helper
+], void 0, {
+    path: "input.js",
+    exportName: "data",
+    kind: "const",
+    line: 4,
+    column: 14
+});
@@ -0,0 +1,4 @@
+import get, { map, filter as select, type Dictionary, Collection } from "lodash";
+import { other } from "other";
+const dictionary: Dictionary<Collection> = {};
+export const result = get(map(select(dictionary, other)));
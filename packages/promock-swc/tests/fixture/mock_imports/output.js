@@ -0,0 +1,36 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import _imported_get, { map as _imported_map, filter as _imported_select, type Dictionary, Collection } from "lodash";
+import { other } from "other";
+const get = mockify(_imported_get, void 0, {
+    path: "input.ts",
+    exportName: "default",
+    kind: "import",
+    importedFrom: "lodash",
+    line: 1,
+    column: 8
+});
+const map = mockify(_imported_map, void 0, {
+    path: "input.ts",
+    exportName: "map",
+    kind: "import",
+    importedFrom: "lodash",
+    line: 1,
+    column: 15
+});
+const select = mockify(_imported_select, void 0, {
+    path: "input.ts",
+    exportName: "filter",
+    kind: "import",
+    importedFrom: "lodash",
+    line: 1,
+    column: 30
+});
+const dictionary: Dictionary<Collection> = {};
+export const result = mockify(get(map(select(dictionary, other))), void 0, {
+    path: "input.ts",
+    exportName: "result",
+    kind: "const",
+    line: 4,
+    column: 14
+});
@@ -0,0 +1,2 @@
+import { api } from "./lib/api.js";
+api();
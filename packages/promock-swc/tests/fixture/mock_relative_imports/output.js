@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import { api as _imported_api } from "./lib/api.js";
+const api = mockify(_imported_api, void 0, {
+    path: "input.js",
+    exportName: "api",
+    kind: "import",
+    importedFrom: "lib/api",
+    line: 1,
+    column: 10
+});
+api();
@@ -0,0 +1,17 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const mockify1 = mockify((value)=>value, void 0, {
+    path: "input.js",
+    exportName: "mockify",
+    kind: "const",
+    line: 1,
+    column: 14
+});
+export { mockify1 as mockify };
+export const restore = mockify(()=>{}, void 0, {
+    path: "input.js",
+    exportName: "restore",
+    kind: "const",
+    line: 2,
+    column: 14
+});
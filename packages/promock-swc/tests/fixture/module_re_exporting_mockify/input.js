@@ -0,0 +1,3 @@
+import { override } from "./override";
+export { mockify } from "./main";
+export { override };
@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import { override } from "./override";
+export { mockify } from "./main";
+export { _mockified_override as override };
+const _mockified_override = mockify(override, void 0, {
+    path: "input.js",
+    exportName: "override",
+    kind: "binding",
+    line: 3,
+    column: 10
+});
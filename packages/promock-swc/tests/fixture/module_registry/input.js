@@ -0,0 +1,5 @@
+export const a = {};
+export function b() {}
+class C {}
+export { a as d, C };
+export default C;
@@ -0,0 +1,58 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_b = mockify(_actual_b, b, {
+    path: "input.js",
+    exportName: "b",
+    kind: "function",
+    line: 2,
+    column: 1
+});
+export const a = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "a",
+    kind: "const",
+    line: 1,
+    column: 14
+});
+function _actual_b() {}
+class C {
+}
+export { a as d, _mockified_C as C };
+const _mockified_C = mockify(C, void 0, {
+    path: "input.js",
+    exportName: "C",
+    kind: "binding",
+    line: 4,
+    column: 18
+});
+function b(...args) {
+    return _mockified_b.apply(this, args);
+}
+export { _mockified_b as b };
+export { _mockified_C as default };
+const _promock_module = {
+    path: "input.js",
+    exports: [
+        {
+            name: "a",
+            kind: "const"
+        },
+        {
+            name: "b",
+            kind: "function"
+        },
+        {
+            name: "d",
+            kind: "binding"
+        },
+        {
+            name: "C",
+            kind: "binding"
+        },
+        {
+            name: "default",
+            kind: "binding"
+        }
+    ]
+};
+export { _promock_module as __promock_module__ };
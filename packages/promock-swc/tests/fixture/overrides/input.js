@@ -0,0 +1,6 @@
+function helper() {
+  return {};
+}
+export function main() {
+  return helper();
+}
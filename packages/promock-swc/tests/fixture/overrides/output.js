@@ -0,0 +1,36 @@
+"use __mockified__";
+const mockify = require("@app/promock").mockify;
+const _mockified_helper = mockify(_actual_helper, helper, {
+    path: "input.js",
+    exportName: "helper",
+    kind: "function",
+    rewired: true,
+    line: 1,
+    column: 1
+});
+const _mockified_main = mockify(_actual_main, main, {
+    path: "input.js",
+    exportName: "main",
+    kind: "function",
+    line: 4,
+    column: 1
+});
+function _actual_helper() {
+    return {};
+}
+function _actual_main() {
+    return helper();
+}
+function helper(...args) {
+    return _mockified_helper.apply(this, args);
+}
+function main(...args) {
+    return _mockified_main.apply(this, args);
+}
+export { _mockified_main as main };
+const _promock_rewire = {
+    get helper () {
+        return _mockified_helper;
+    }
+};
+export { _promock_rewire as __promock_rewire__ };
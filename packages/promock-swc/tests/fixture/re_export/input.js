@@ -0,0 +1 @@
+export { example } from 'another-module';
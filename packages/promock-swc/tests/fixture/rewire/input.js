@@ -0,0 +1,7 @@
+const config = { retries: 3 };
+function fetchData() { return config; }
+class Client {}
+let counter = 0;
+const client = new Client();
+export function run() { return fetchData(); }
+export { client };
@@ -0,0 +1,71 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_fetchData = mockify(_actual_fetchData, fetchData, {
+    path: "input.js",
+    exportName: "fetchData",
+    kind: "function",
+    rewired: true,
+    line: 2,
+    column: 1
+});
+const _mockified_run = mockify(_actual_run, run, {
+    path: "input.js",
+    exportName: "run",
+    kind: "function",
+    line: 6,
+    column: 1
+});
+const config = mockify({
+    retries: 3
+}, void 0, {
+    path: "input.js",
+    exportName: "config",
+    kind: "const",
+    rewired: true,
+    line: 1,
+    column: 7
+});
+function _actual_fetchData() {
+    return config;
+}
+const Client = mockify(class Client {
+}, void 0, {
+    path: "input.js",
+    exportName: "Client",
+    kind: "class",
+    rewired: true,
+    line: 3,
+    column: 1
+});
+let counter = 0;
+const client = new Client();
+function _actual_run() {
+    return fetchData();
+}
+export { _mockified_client as client };
+function fetchData(...args) {
+    return _mockified_fetchData.apply(this, args);
+}
+function run(...args) {
+    return _mockified_run.apply(this, args);
+}
+export { _mockified_run as run };
+const _mockified_client = mockify(client, void 0, {
+    path: "input.js",
+    exportName: "client",
+    kind: "binding",
+    line: 7,
+    column: 10
+});
+const _promock_rewire = {
+    get config () {
+        return config;
+    },
+    get fetchData () {
+        return _mockified_fetchData;
+    },
+    get Client () {
+        return Client;
+    }
+};
+export { _promock_rewire as __promock_rewire__ };
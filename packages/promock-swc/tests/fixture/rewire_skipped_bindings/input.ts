@@ -0,0 +1,12 @@
+declare function ambient(): void;
+declare class AmbientClass {}
+let mutable = {};
+const { destructured } = { destructured: {} };
+const primitive = 1;
+function helper() {
+  return [mutable, destructured, primitive];
+}
+export function main() {
+  ambient();
+  return helper();
+}
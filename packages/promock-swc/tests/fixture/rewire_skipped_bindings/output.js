@@ -0,0 +1,59 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_helper = mockify(_actual_helper, helper, {
+    path: "input.ts",
+    exportName: "helper",
+    kind: "function",
+    rewired: true,
+    line: 6,
+    column: 1
+});
+const _mockified_main = mockify(_actual_main, main, {
+    path: "input.ts",
+    exportName: "main",
+    kind: "function",
+    line: 9,
+    column: 1
+});
+declare function ambient(): void;
+declare class AmbientClass {
+}
+let mutable = {};
+const { destructured } = {
+    destructured: {}
+};
+const primitive = mockify(1, void 0, {
+    path: "input.ts",
+    exportName: "primitive",
+    kind: "const",
+    rewired: true,
+    line: 5,
+    column: 7
+});
+function _actual_helper() {
+    return [
+        mutable,
+        destructured,
+        primitive
+    ];
+}
+function _actual_main() {
+    ambient();
+    return helper();
+}
+function helper(...args) {
+    return _mockified_helper.apply(this, args);
+}
+function main(...args) {
+    return _mockified_main.apply(this, args);
+}
+export { _mockified_main as main };
+const _promock_rewire = {
+    get primitive () {
+        return primitive;
+    },
+    get helper () {
+        return _mockified_helper;
+    }
+};
+export { _promock_rewire as __promock_rewire__ };
@@ -0,0 +1,7 @@
+const store = {};
+function load() {
+  return store;
+}
+export function get() {
+  return load();
+}
@@ -0,0 +1,57 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_load = mockify(_actual_load, load, {
+    path: "input.js",
+    exportName: "load",
+    kind: "function",
+    rewired: true,
+    line: 2,
+    column: 1
+});
+const _mockified_get = mockify(_actual_get, get, {
+    path: "input.js",
+    exportName: "get",
+    kind: "function",
+    line: 5,
+    column: 1
+});
+const store = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "store",
+    kind: "const",
+    rewired: true,
+    line: 1,
+    column: 7
+});
+function _actual_load() {
+    return store;
+}
+function _actual_get() {
+    return load();
+}
+function load(...args) {
+    return _mockified_load.apply(this, args);
+}
+function get(...args) {
+    return _mockified_get.apply(this, args);
+}
+export { _mockified_get as get };
+const _promock_module = {
+    path: "input.js",
+    exports: [
+        {
+            name: "get",
+            kind: "function"
+        }
+    ]
+};
+export { _promock_module as __promock_module__ };
+const _promock_rewire = {
+    get store () {
+        return store;
+    },
+    get load () {
+        return _mockified_load;
+    }
+};
+export { _promock_rewire as __promock_rewire__ };
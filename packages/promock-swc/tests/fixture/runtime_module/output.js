@@ -0,0 +1,2 @@
+export const mockify = (value)=>value;
+export const restore = ()=>{};
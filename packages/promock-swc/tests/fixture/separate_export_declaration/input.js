@@ -0,0 +1,3 @@
+const A = () => {};
+function B() {}
+export { A, B };
@@ -0,0 +1,19 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_B = mockify(B, void 0, {
+    path: "input.js",
+    exportName: "B",
+    kind: "binding",
+    line: 3,
+    column: 13
+});
+const A = ()=>{};
+function B() {}
+export { _mockified_A as A, _mockified_B as B };
+const _mockified_A = mockify(A, void 0, {
+    path: "input.js",
+    exportName: "A",
+    kind: "binding",
+    line: 3,
+    column: 10
+});
@@ -0,0 +1,19 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_B = mockify(B, void 0, {
+    path: "input.js",
+    exportName: "BB",
+    kind: "binding",
+    line: 3,
+    column: 19
+});
+const A = ()=>{};
+function B() {}
+export { _mockified_A as AA, _mockified_B as BB };
+const _mockified_A = mockify(A, void 0, {
+    path: "input.js",
+    exportName: "AA",
+    kind: "binding",
+    line: 3,
+    column: 10
+});
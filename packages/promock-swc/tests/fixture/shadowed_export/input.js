@@ -0,0 +1,11 @@
+function A() {}
+{
+    const A = 1;
+    const _mockified_A = 2;
+}
+function B() {
+    const A = 3;
+    return A;
+}
+export { A, B };
+export default A;
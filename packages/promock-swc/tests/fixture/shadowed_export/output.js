@@ -0,0 +1,26 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_A = mockify(A, void 0, {
+    path: "input.js",
+    exportName: "A",
+    kind: "binding",
+    line: 10,
+    column: 10
+});
+const _mockified_B = mockify(B, void 0, {
+    path: "input.js",
+    exportName: "B",
+    kind: "binding",
+    line: 10,
+    column: 13
+});
+function A() {}
+{
+    const A = 1;
+    const _mockified_A = 2;
+}function B() {
+    const A = 3;
+    return A;
+}
+export { _mockified_A as A, _mockified_B as B };
+export { _mockified_A as default };
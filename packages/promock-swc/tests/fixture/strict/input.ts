@@ -0,0 +1,5 @@
+export let mutable = {};
+export declare const ambient: {};
+export { reExported } from "./other.js";
+export type Type = string;
+export const value = {};
@@ -0,0 +1,14 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+export let mutable = {};
+export declare const ambient: {
+};
+export { reExported } from "./other.js";
+export type Type = string;
+export const value = mockify({}, void 0, {
+    path: "input.ts",
+    exportName: "value",
+    kind: "const",
+    line: 5,
+    column: 14
+});
@@ -0,0 +1,2 @@
+export let mutable = {};
+export var legacy = {};
@@ -0,0 +1,4 @@
+const source = { a: {}, b: {} };
+const list = [{}];
+export const { a, b } = source;
+export const [c] = list;
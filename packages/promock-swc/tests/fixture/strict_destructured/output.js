@@ -0,0 +1,9 @@
+const source = {
+    a: {},
+    b: {}
+};
+const list = [
+    {}
+];
+export const { a, b } = source;
+export const [c] = list;
@@ -0,0 +1,3 @@
+const a = {};
+function b() {}
+export { a as "foo-bar", b as "🙂" };
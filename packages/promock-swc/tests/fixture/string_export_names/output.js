@@ -0,0 +1,19 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_b = mockify(b, void 0, {
+    path: "input.js",
+    exportName: "🙂",
+    kind: "binding",
+    line: 3,
+    column: 26
+});
+const a = {};
+function b() {}
+export { _mockified_a as "foo-bar", _mockified_b as "🙂" };
+const _mockified_a = mockify(a, void 0, {
+    path: "input.js",
+    exportName: "foo-bar",
+    kind: "binding",
+    line: 3,
+    column: 10
+});
@@ -0,0 +1,3 @@
+export function f() {}
+export const c = {};
+export { f as "f alias", c as "c-alias" };
@@ -0,0 +1,22 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const _mockified_f = mockify(_actual_f, f, {
+    path: "input.js",
+    exportName: "f",
+    kind: "function",
+    line: 1,
+    column: 1
+});
+function _actual_f() {}
+export const c = mockify({}, void 0, {
+    path: "input.js",
+    exportName: "c",
+    kind: "const",
+    line: 2,
+    column: 14
+});
+export { _mockified_f as "f alias", c as "c-alias" };
+function f(...args) {
+    return _mockified_f.apply(this, args);
+}
+export { _mockified_f as f };
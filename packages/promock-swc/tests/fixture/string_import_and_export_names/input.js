@@ -0,0 +1,3 @@
+import { "foo-bar" as fooBar } from "./module";
+export { fooBar as "foo-bar" };
+export { "baz-qux" as bazQux, "🙂" } from "./module";
@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+import { "foo-bar" as fooBar } from "./module";
+export { _mockified_fooBar as "foo-bar" };
+export { "baz-qux" as bazQux, "🙂" } from "./module";
+const _mockified_fooBar = mockify(fooBar, void 0, {
+    path: "input.js",
+    exportName: "foo-bar",
+    kind: "binding",
+    line: 2,
+    column: 10
+});
@@ -0,0 +1,3 @@
+const A = {};
+type B = string;
+export { A, type B };
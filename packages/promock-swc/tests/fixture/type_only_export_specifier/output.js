@@ -0,0 +1,12 @@
+"use __mockified__";
+import { mockify as mockify } from "promock";
+const A = {};
+type B = string;
+export { _mockified_A as A, type B };
+const _mockified_A = mockify(A, void 0, {
+    path: "input.ts",
+    exportName: "A",
+    kind: "binding",
+    line: 3,
+    column: 10
+});
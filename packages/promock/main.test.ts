@@ -6,7 +6,19 @@ import MockClass, {
   expression,
 } from "./fixtures/content.js";
 import defaultFn from "./fixtures/defaultFn.js";
-import { isMockified, override, partialOverride, restore } from "./main.js";
+import {
+  getMockifiedExports,
+  getRewired,
+  installGlobalRuntime,
+  isMockified,
+  mockify,
+  override,
+  partialOverride,
+  restore,
+  restoreModule,
+  restoreRewired,
+  rewire,
+} from "./main.js";
 import { afterEach, expect, describe, it } from "@jest/globals";
 
 describe("validate", () => {
@@ -98,3 +110,145 @@ describe("validate", () => {
     expect(expression).toBe(400);
   });
 });
+
+describe("shared imports", () => {
+  const importOf = (importedFrom: string, exportName: string) => ({
+    exportName,
+    kind: "import",
+    importedFrom,
+  });
+
+  it("shares the proxy of an import between the importing modules", () => {
+    const get = () => 100;
+    const inFirstModule = mockify(get, undefined, importOf("shared", "get"));
+    const inSecondModule = mockify(get, undefined, importOf("shared", "get"));
+    expect(inSecondModule).toBe(inFirstModule);
+
+    override(inFirstModule, () => 200);
+    expect(inSecondModule()).toBe(200);
+    restore(inFirstModule);
+    expect(inSecondModule()).toBe(100);
+  });
+
+  it("keeps the proxies of other imports apart", () => {
+    const get = () => 100;
+    const proxy = mockify(get, undefined, importOf("separate", "get"));
+    const otherName = mockify(get, undefined, importOf("separate", "default"));
+    const otherModule = mockify(get, undefined, importOf("other", "get"));
+    expect(otherName).not.toBe(proxy);
+    expect(otherModule).not.toBe(proxy);
+    expect(otherModule).not.toBe(otherName);
+
+    override(proxy, () => 200);
+    expect(otherName()).toBe(100);
+    expect(otherModule()).toBe(100);
+    restore(proxy);
+  });
+});
+
+describe("installGlobalRuntime", () => {
+  const runtimeGlobal = globalThis as { __promock__?: { mockify: unknown } };
+
+  afterEach(() => {
+    delete runtimeGlobal.__promock__;
+  });
+
+  it("exposes mockify as globalThis.__promock__", () => {
+    expect(runtimeGlobal.__promock__).toBeUndefined();
+    installGlobalRuntime();
+    expect(runtimeGlobal.__promock__?.mockify).toBe(mockify);
+  });
+});
+
+describe("module registry", () => {
+  // the namespace of a module transformed with the `moduleRegistry` option
+  const moduleNamespace = () => {
+    const Utils = { format: mockify((value: string) => value) };
+    return {
+      config: mockify({ debug: false }),
+      load: mockify(() => "loaded"),
+      Utils,
+      version: "1.0.0",
+      __promock_module__: {
+        path: "src/module.ts",
+        exports: [
+          { name: "config", kind: "const" },
+          { name: "load", kind: "function" },
+          { name: "Utils.format", kind: "function" },
+        ],
+      },
+    };
+  };
+
+  it("lists the mockified exports of the registry", () => {
+    const namespace = moduleNamespace();
+    const mockifiedExports = getMockifiedExports(namespace);
+    expect([...mockifiedExports.keys()]).toEqual([
+      "config",
+      "load",
+      "Utils.format",
+    ]);
+    expect(mockifiedExports.get("config")).toBe(namespace.config);
+    expect(mockifiedExports.get("Utils.format")).toBe(namespace.Utils.format);
+  });
+
+  it("restores every export of the module", () => {
+    const namespace = moduleNamespace();
+    override(namespace.config, { debug: true });
+    override(namespace.load, () => "overridden");
+    override(namespace.Utils.format, () => "overridden");
+
+    restoreModule(namespace);
+    expect(namespace.config.debug).toBe(false);
+    expect(namespace.load()).toBe("loaded");
+    expect(namespace.Utils.format("value")).toBe("value");
+  });
+
+  it("requires the registry", () => {
+    expect(() => getMockifiedExports({ config: mockify({}) })).toThrow(
+      "moduleRegistry",
+    );
+  });
+});
+
+describe("rewire", () => {
+  // a module transformed with the `rewire` option, whose exported `get` calls the function `load` it doesn't export
+  const moduleNamespace = () => {
+    function _actual_load() {
+      return "loaded";
+    }
+    function load(this: unknown, ...args: []) {
+      return _mockified_load.apply(this, args);
+    }
+    const _mockified_load = mockify(_actual_load, load);
+    const get = mockify(() => load());
+    return {
+      get,
+      __promock_rewire__: {
+        get load() {
+          return _mockified_load;
+        },
+      },
+    };
+  };
+
+  it("overrides and restores the bindings that are not exported", () => {
+    const namespace = moduleNamespace();
+    const load = getRewired<() => string>(namespace, "load");
+    expect(isMockified(load)).toBe(true);
+    expect(namespace.get()).toBe("loaded");
+
+    rewire(namespace, "load", () => "rewired");
+    expect(load()).toBe("rewired");
+    expect(namespace.get()).toBe("rewired");
+
+    restoreRewired(namespace, "load");
+    expect(namespace.get()).toBe("loaded");
+  });
+
+  it("requires the binding to be rewired", () => {
+    const namespace = moduleNamespace();
+    expect(() => getRewired(namespace, "store")).toThrow('"store"');
+    expect(() => getRewired({}, "load")).toThrow('"rewire"');
+  });
+});
@@ -17,13 +17,47 @@ type Configuration<T> = {
   propertyDescriptorSource: "default" | "override";
   instances: Set<WeakRef<object>>;
   readonly defaultImplementation: T;
+  readonly metadata?: MockifyMetadata;
 };
 
 type InternalWrapper = object | ((...args: unknown[]) => unknown);
 
+/**
+ * Describes where a mockified value comes from, passed by the SWC plugin to every `mockify` call.
+ */
+export type MockifyMetadata = {
+  /** Path of the module, relative to the `basePath` of the plugin */
+  path?: string;
+  /** Name the value is exported under, e.g. `default` or `Namespace.member` */
+  exportName: string;
+  /** The kind of the exported declaration, e.g. `function` or `const` */
+  kind: string;
+  /** For imports mockified by the importing module, the module they're imported from */
+  importedFrom?: string;
+  line?: number;
+  column?: number;
+};
+
+const describe = (metadata: MockifyMetadata | undefined): string => {
+  if (!metadata) return "a mockified object";
+  const location = [metadata.path, metadata.line, metadata.column]
+    .filter((part) => part !== undefined)
+    .join(":");
+  return location
+    ? `"${metadata.exportName}" (${location})`
+    : `"${metadata.exportName}"`;
+};
+
+/**
+ * Proxies of the imports mockified by the importing modules (`mockImports` option of the SWC plugin),
+ * shared by all the modules importing them, keyed by the imported module and the name of the import.
+ */
+const sharedImports = new Map<string, object>();
+
 export const mockify = <T extends object>(
   obj: T,
   internalFnWrapper?: InternalWrapper,
+  metadata?: MockifyMetadata,
 ): T => {
   if (!obj || (typeof obj !== "function" && typeof obj !== "object")) {
     return obj;
@@ -39,12 +73,22 @@ export const mockify = <T extends object>(
   // noop if previously mockified
   if (isMockified(obj)) return obj;
 
+  const sharedImportKey =
+    metadata?.importedFrom !== undefined
+      ? `${metadata.importedFrom}#${metadata.exportName}`
+      : undefined;
+  const sharedImport = sharedImportKey && sharedImports.get(sharedImportKey);
+  if (sharedImport) {
+    return sharedImport as T;
+  }
+
   const conf: Configuration<T> = {
     implementation: undefined,
     partial: false,
     propertyDescriptorSource: "override",
     defaultImplementation: obj,
     instances: new Set(),
+    metadata,
   };
 
   const hasInternalFnWrapper =
@@ -110,7 +154,7 @@ export const mockify = <T extends object>(
     set(target, propertyKey, newValue, receiver) {
       if (propertyKey === configuration) {
         throw new Error(
-          'Overriding the "configuration" property is not allowed. Mutate the configuration object instead.',
+          `Overriding the "configuration" property of ${describe(metadata)} is not allowed. Mutate the configuration object instead.`,
         );
       }
       const t = conf.implementation ?? target;
@@ -241,9 +285,23 @@ export const mockify = <T extends object>(
       internalWrapperForProxy
     ] = mockProxy;
   }
+  if (sharedImportKey) {
+    sharedImports.set(sharedImportKey, mockProxy);
+  }
   return mockProxy;
 };
 
+/**
+ * Exposes the runtime as `globalThis.__promock__`,
+ * for modules transformed with the "global" or "inline" runtime of the SWC plugin.
+ * Must be called before any of these modules is evaluated (e.g. in a test setup file).
+ */
+export const installGlobalRuntime = (): void => {
+  (globalThis as { __promock__?: { mockify: typeof mockify } }).__promock__ = {
+    mockify,
+  };
+};
+
 export const isMockified = <T extends object>(
   obj: T | null,
 ): obj is T & {
@@ -379,3 +437,100 @@ export function restore<T extends object>(
 
 export const getActual = <T extends object>(source: T): T =>
   getMockConfig(source, false)?.defaultImplementation ?? source;
+
+/**
+ * Lists the mockified exports of a module, as exported by the SWC plugin with the `moduleRegistry` option.
+ */
+export type ModuleRegistry = {
+  path?: string;
+  exports: { name: string; kind: string }[];
+};
+
+const moduleRegistryExport = "__promock_module__";
+
+/**
+ * Returns the registry of a module namespace (`import * as module from "./module"`),
+ * or undefined if the module was not transformed with the `moduleRegistry` option.
+ */
+export const getModuleRegistry = (
+  namespace: object,
+): ModuleRegistry | undefined =>
+  (namespace as { [moduleRegistryExport]?: ModuleRegistry })[
+    moduleRegistryExport
+  ];
+
+/**
+ * Returns the mockified exports of a module namespace, by their export names.
+ * Members of namespaces are listed under their qualified names, e.g. `Utils.fn`.
+ */
+export function getMockifiedExports(namespace: object): Map<string, object> {
+  const registry = getModuleRegistry(namespace);
+  if (!registry) {
+    throw new Error(
+      'Cannot list the exports of a module without a registry. Enable the "moduleRegistry" option of the SWC plugin.',
+    );
+  }
+  const mockifiedExports = new Map<string, object>();
+  for (const { name } of registry.exports) {
+    const value = name
+      .split(".")
+      .reduce<unknown>(
+        (parent, key) => (parent as Record<string, unknown>)?.[key],
+        namespace,
+      );
+    if (isMockified(value as object | null)) {
+      mockifiedExports.set(name, value as object);
+    }
+  }
+  return mockifiedExports;
+}
+
+/**
+ * Restores all the mockified exports of a module namespace.
+ */
+export function restoreModule(namespace: object): void {
+  getMockifiedExports(namespace).forEach((value) => restore(value));
+}
+
+const rewireExport = "__promock_rewire__";
+
+/**
+ * Returns the proxy of a binding that is not exported from a module namespace,
+ * as exposed by the SWC plugin with the `rewire` option.
+ */
+export function getRewired<T extends object>(namespace: object, name: string): T {
+  const accessor = (namespace as { [rewireExport]?: Record<string, T> })[
+    rewireExport
+  ];
+  if (!accessor || !(name in accessor)) {
+    throw new Error(
+      `Cannot rewire "${name}", as it's not a module-level function, class or const of a module transformed with the "rewire" option of the SWC plugin.`,
+    );
+  }
+  return accessor[name]!;
+}
+
+/**
+ * Overrides a binding that is not exported from a module namespace, by its name.
+ */
+export function rewire<T extends object>(
+  namespace: object,
+  name: string,
+  replacement: T,
+): { [dispose](): void } {
+  return override(getRewired<T>(namespace, name), replacement);
+}
+
+/**
+ * Restores a binding that is not exported from a module namespace, by its name.
+ */
+export function restoreRewired(namespace: object, name: string): void {
+  restore(getRewired(namespace, name));
+}
+
+/**
+ * Returns where the mockified export comes from, as passed by the SWC plugin.
+ */
+export const getMetadata = <T extends object>(
+  source: T,
+): MockifyMetadata | undefined => getMockConfig(source, false)?.metadata;
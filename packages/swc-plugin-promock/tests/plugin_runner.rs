@@ -0,0 +1,265 @@
+//! Runs the compiled plugin through the plugin runner of swc, the same as swc does,
+//! with the config and the metadata (filename, cwd, env) passed to `process_transform`.
+//!
+//! The plugin is built for `wasm32-wasip1` on the first test (`rustup target add wasm32-wasip1`),
+//! in release mode, as the debug build is too big for the runner to compile it in a reasonable time.
+//! As that takes a while, the tests are ignored by default, run them with `pnpm test:plugin-runner`.
+//!
+//! The wasmer of the plugin runner refers to the `__rust_probestack` of compiler-builtins on x86 outside of Windows,
+//! for the stack probes of the compiled plugins, which recent Rust versions no longer export,
+//! and the runners using a wasmer that doesn't need it require a newer swc_core than the plugin's.
+//! So the tests only exist on the other targets (e.g. aarch64), where wasmer probes the stack itself.
+#![cfg(not(all(any(target_arch = "x86_64", target_arch = "x86"), not(windows))))]
+
+use std::{
+    env, fs,
+    path::Path,
+    process::Command,
+    sync::{Arc, LazyLock},
+};
+
+use serde_json::{json, Value};
+use swc_core::{
+    common::{
+        errors::{ColorConfig, Handler, HANDLER},
+        plugin::{
+            metadata::TransformPluginMetadataContext,
+            serialized::{PluginSerializedBytes, VersionedSerializable},
+        },
+        FileName, Mark, SourceMap, GLOBALS,
+    },
+    ecma::{
+        ast::{EsVersion, Program},
+        codegen::{text_writer::JsWriter, Emitter},
+        parser::{parse_file_as_module, Syntax},
+        transforms::base::resolver,
+        visit::FoldWith,
+    },
+};
+use swc_plugin_runner::{
+    create_plugin_transform_executor, plugin_module_bytes::CompiledPluginModuleBytes,
+};
+use wasmer::Store;
+
+/// The working directory of swc, which a relative `basePath` is resolved against
+const CWD: &str = "/project";
+
+/// The plugin, compiled once for all the tests
+static PLUGIN: LazyLock<CompiledPluginModuleBytes> = LazyLock::new(|| {
+    // a target directory of its own, as the one of the tests is locked by the cargo running them
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("plugin");
+    let status = Command::new(env::var_os("CARGO").unwrap_or("cargo".into()))
+        .args(["build", "--release", "--package", "swc-plugin-promock"])
+        .args(["--target", "wasm32-wasip1", "--target-dir"])
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "failed to build the plugin");
+
+    let path = target_dir.join("wasm32-wasip1/release/swc_plugin_promock.wasm");
+    let bytes = fs::read(&path).expect("failed to read the plugin");
+    let store = Store::default();
+    let module = wasmer::Module::new(&store, bytes).expect("failed to compile the plugin");
+    CompiledPluginModuleBytes::new(path.to_string_lossy().into_owned(), module, store)
+});
+
+/// Runs the plugin on the module, as swc would in the environment `env`,
+/// returning the transformed module, or the error of the plugin runner (e.g. when the plugin panics).
+fn transform(config: Value, filename: &str, env: &str, source: &str) -> Result<String, String> {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _guard = runtime.enter();
+
+    let cm: Arc<SourceMap> = Default::default();
+    let handler = Handler::with_tty_emitter(ColorConfig::Never, true, false, Some(cm.clone()));
+    GLOBALS.set(&Default::default(), || {
+        HANDLER.set(&handler, || {
+            let fm = cm.new_source_file(FileName::Real(filename.into()).into(), source.into());
+            let module = parse_file_as_module(
+                &fm,
+                Syntax::Es(Default::default()),
+                EsVersion::latest(),
+                None,
+                &mut vec![],
+            )
+            .expect("failed to parse the module");
+
+            // swc runs the resolver before the plugins
+            let unresolved_mark = Mark::new();
+            let program = Program::Module(module).fold_with(&mut resolver(
+                unresolved_mark,
+                Mark::new(),
+                false,
+            ));
+            let program =
+                PluginSerializedBytes::try_serialize(&VersionedSerializable::new(program)).unwrap();
+
+            let mut metadata =
+                TransformPluginMetadataContext::new(Some(filename.into()), env.into(), None);
+            metadata.cwd = Some(CWD.into());
+            let mut executor = create_plugin_transform_executor(
+                &cm,
+                &unresolved_mark,
+                &Arc::new(metadata),
+                Box::new(PLUGIN.clone()),
+                Some(config),
+                None,
+            );
+            let program: Program = executor
+                .transform(&program, Some(false))
+                .map_err(|error| format!("{error:?}"))?
+                .deserialize()
+                .unwrap()
+                .into_inner();
+
+            let mut code = vec![];
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                comments: None,
+                wr: JsWriter::new(cm.clone(), "\n", &mut code, None),
+            };
+            emitter.emit_program(&program).unwrap();
+            Ok(String::from_utf8(code).unwrap())
+        })
+    })
+}
+
+const MODULE: &str = "export const example = {};\n";
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn transforms_included_modules() {
+    let output = transform(
+        json!({ "basePath": CWD, "includePaths": ["^src/"] }),
+        "/project/src/example.js",
+        "test",
+        MODULE,
+    )
+    .unwrap();
+
+    assert!(
+        output.contains("import { mockify as mockify } from \"promock\";"),
+        "{output}"
+    );
+    assert!(output.contains("path: \"src/example.js\""), "{output}");
+    assert!(output.contains("line: 1"), "{output}");
+}
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn leaves_modules_outside_of_include_paths() {
+    let output = transform(
+        json!({ "basePath": CWD, "includePaths": ["^src/"] }),
+        "/project/scripts/build.js",
+        "test",
+        MODULE,
+    )
+    .unwrap();
+
+    assert_eq!(output, MODULE);
+}
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn leaves_excluded_modules() {
+    let output = transform(
+        json!({ "basePath": CWD, "excludePaths": ["^src/vendor/"] }),
+        "/project/src/vendor/library.js",
+        "test",
+        MODULE,
+    )
+    .unwrap();
+
+    assert_eq!(output, MODULE);
+}
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn resolves_relative_base_path_against_cwd() {
+    let output = transform(
+        json!({ "basePath": "./src" }),
+        "/project/src/example.js",
+        "test",
+        MODULE,
+    )
+    .unwrap();
+
+    assert!(output.contains("path: \"example.js\""), "{output}");
+}
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn only_transforms_in_envs() {
+    let config = json!({ "basePath": CWD, "envs": ["test"] });
+
+    let output = transform(
+        config.clone(),
+        "/project/src/example.js",
+        "production",
+        MODULE,
+    );
+    assert_eq!(output.unwrap(), MODULE);
+
+    let output = transform(config, "/project/src/example.js", "test", MODULE);
+    assert!(output.unwrap().contains("mockify"));
+}
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn parses_config() {
+    let output = transform(
+        json!({
+            "basePath": CWD,
+            "importFrom": "@company/promock",
+            "runtime": "require",
+            "mockImports": ["^lodash$"],
+        }),
+        "/project/src/example.js",
+        "test",
+        "import { debounce } from \"lodash\";\nexport const example = { debounce };\n",
+    )
+    .unwrap();
+
+    assert!(output.contains("require(\"@company/promock\")"), "{output}");
+    assert!(output.contains("importedFrom: \"lodash\""), "{output}");
+}
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn applies_overrides_of_paths() {
+    let config = json!({
+        "basePath": CWD,
+        "overrides": [{ "paths": ["^packages/app/"], "importFrom": "@app/promock" }],
+    });
+
+    let output = transform(
+        config.clone(),
+        "/project/packages/app/example.js",
+        "test",
+        MODULE,
+    );
+    assert!(output.unwrap().contains("from \"@app/promock\""));
+
+    let output = transform(config, "/project/packages/lib/example.js", "test", MODULE);
+    assert!(output.unwrap().contains("from \"promock\""));
+}
+
+#[test]
+#[ignore = "builds the plugin, run with `pnpm test:plugin-runner`"]
+fn fails_on_invalid_config() {
+    assert!(transform(
+        json!({ "basePath": CWD, "unknown": true }),
+        "/project/src/example.js",
+        "test",
+        MODULE
+    )
+    .is_err());
+    assert!(transform(
+        json!({ "basePath": CWD, "excludePaths": ["("] }),
+        "/project/src/example.js",
+        "test",
+        MODULE
+    )
+    .is_err());
+    assert!(transform(json!({}), "/project/src/example.js", "test", MODULE).is_err());
+}